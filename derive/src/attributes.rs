@@ -0,0 +1,618 @@
+use crate::expression;
+use crate::path::Path;
+use iref::Iri;
+use proc_macro_error::emit_error;
+use proc_macro2::{TokenStream, TokenTree};
+use syn::parse::{ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{
+  Attribute, Data, DeriveInput, Expr, ExprLit, Generics, Lit, LitStr, MetaNameValue, Token,
+};
+
+/// The options of the SPARQL derive.
+///
+/// The `#[ld(...)]` attributes, shared with `linked-data-core` and
+/// `linked_data_next`, are only read; the options only known to this derive
+/// are written in `#[sparql(...)]` attributes.
+#[derive(Default)]
+pub struct Options {
+  pub generics: Generics,
+  /// `#[ld(prefix("ex" = "http://example.org/"))]`.
+  pub prefixes: Vec<(String, LitStr)>,
+  /// `#[ld(type = "...")]`, and `#[sparql(type = "...")]` or
+  /// `#[sparql(type = ["...", ...])]`, on the container: the resources must
+  /// have all of them.
+  pub types: Vec<LitStr>,
+  /// `#[sparql(type_match = "subclass")]`: instances of subclasses of the
+  /// types match too, through `rdf:type/rdfs:subClassOf*`.
+  pub match_subclasses: bool,
+  /// `#[sparql(transparent)]`, on a struct with a single field.
+  pub transparent: bool,
+  /// `#[sparql(property_paths)]`: chained variant predicates are matched
+  /// with a single sequence path rather than a pattern per hop.
+  pub property_paths: bool,
+  pub fields: Vec<FieldOptions>,
+  /// The types of the fields built with their `ToConstructQuery`
+  /// implementation: neither ignored nor built `sparql_with` a function.
+  pub pattern_types: Vec<syn::Type>,
+  pub variants: Vec<FieldOptions>,
+  pub variant_fields: Vec<Vec<FieldOptions>>,
+}
+
+/// The aggregate functions of aggregate fields.
+const AGGREGATES: [&str; 5] = ["count", "sum", "min", "max", "sample"];
+
+/// The `#[sparql(...)]` options of containers, rejected in `#[ld(...)]`.
+const CONTAINER_OPTIONS: [&str; 3] = ["transparent", "property_paths", "type_match"];
+
+/// The `#[sparql(...)]` options of fields, rejected in `#[ld(...)]`.
+const FIELD_OPTIONS: [&str; 11] = [
+  "path",
+  "lang",
+  "alias",
+  "inverse",
+  "sparql_with",
+  "bind",
+  "count",
+  "sum",
+  "min",
+  "max",
+  "sample",
+];
+
+#[derive(Clone, Default)]
+pub struct FieldOptions {
+  /// The name of the field, for named fields.
+  pub ident: Option<syn::Ident>,
+  /// The `#[ld(...)]` predicate IRI or compact IRI.
+  pub predicate: Option<LitStr>,
+  /// `path = "ex:a/ex:b"`: the property path matching the field, which
+  /// also holds its aliases. The matches are written under the field
+  /// predicate, and the intermediate hops of the path are not constructed.
+  pub path: Option<Path>,
+  /// `alias = "schema:name"`: other predicates matched for the field, written
+  /// with the main one in the CONSTRUCT template.
+  pub aliases: Vec<LitStr>,
+  /// `inverse = "ex:author"`: the field holds the subjects of the triples
+  /// pointing to the resource, matched through the reverse path.
+  pub inverse: Option<LitStr>,
+  pub id: bool,
+  pub flatten: bool,
+  pub ignore: bool,
+  /// `lang = "en"`, in order of preference.
+  pub languages: Vec<LitStr>,
+  /// `sparql_with = "path::to::fn"`, called instead of the
+  /// `ToConstructQuery` implementation of the field type.
+  pub sparql_with: Option<syn::Path>,
+  /// `bind = "YEAR(?birth)"`: the field is computed in the query from its
+  /// sibling fields.
+  pub bind: Option<LitStr>,
+  /// `count = "ex:follower"`, or `sum`, `min`, `max` or `sample`, with the
+  /// predicate or property path to aggregate.
+  pub aggregate: Option<(syn::Path, LitStr)>,
+  /// `count(distinct) = "ex:follower"`: the aggregate only takes each
+  /// distinct object once.
+  pub distinct: bool,
+  /// Whether a `bind` expression refers to the field, whose value is then
+  /// bound to a variable named after it.
+  pub referenced: bool,
+}
+
+impl Options {
+  pub fn parse(input: &DeriveInput) -> Self {
+    let mut options = Self {
+      generics: input.generics.clone(),
+      ..Self::default()
+    };
+
+    for_each_item(&input.attrs, "ld", |item| options.parse_ld_item(item));
+    for_each_item(&input.attrs, "sparql", |item| options.parse_item(item));
+
+    match &input.data {
+      Data::Struct(data) => {
+        for field in &data.fields {
+          let mut field_options = FieldOptions::parse(&field.attrs, &options.prefixes);
+          field_options.ident = field.ident.clone();
+          if field_options.is_pattern() {
+            options.pattern_types.push(field.ty.clone());
+          }
+          options.fields.push(field_options);
+        }
+
+        let referenced = options
+          .fields
+          .iter()
+          .filter_map(|field| field.bind.as_ref())
+          .flat_map(|bind| expression::variables(&bind.value()))
+          .collect::<Vec<_>>();
+        for field in &mut options.fields {
+          field.referenced = field.name().is_some_and(|name| referenced.contains(&name));
+        }
+      }
+      Data::Enum(data) => {
+        for variant in &data.variants {
+          let variant_options = FieldOptions::parse(&variant.attrs, &options.prefixes);
+          let field_options = variant
+            .fields
+            .iter()
+            .map(|field| {
+              let field_options = FieldOptions::parse(&field.attrs, &options.prefixes);
+              if field_options.is_pattern() {
+                options.pattern_types.push(field.ty.clone());
+              }
+              field_options
+            })
+            .collect();
+          options.variants.push(variant_options);
+          options.variant_fields.push(field_options);
+        }
+      }
+      Data::Union(_) => {}
+    }
+
+    options
+  }
+
+  /// Reports the malformed IRIs, undeclared prefixes and meaningless
+  /// combinations of options.
+  pub fn validate(&self) {
+    for (_, namespace) in &self.prefixes {
+      if Iri::new(&namespace.value()).is_err() {
+        emit_error!(namespace, "invalid IRI `{}`", namespace.value());
+      }
+    }
+
+    for type_iri in &self.types {
+      self.validate_iri(type_iri);
+    }
+
+    for field in self
+      .fields
+      .iter()
+      .chain(&self.variants)
+      .chain(self.variant_fields.iter().flatten())
+    {
+      if let Some(predicate) = &field.predicate {
+        self.validate_iri(predicate);
+        if let Some(language) = field.languages.first().filter(|_| field.path.is_some()) {
+          emit_error!(
+            language,
+            "`lang` is not supported with property paths or aliases"
+          );
+        }
+
+        if field.flatten {
+          emit_error!(predicate, "a `flatten` field cannot have a predicate");
+        }
+        if field.id {
+          emit_error!(predicate, "an `id` field cannot have a predicate");
+        }
+      } else if let Some(language) = field.languages.first() {
+        emit_error!(language, "`lang` requires a predicate");
+      }
+
+      if field.path.is_some() && field.predicate.is_none() {
+        emit_error!(
+          proc_macro2::Span::call_site(),
+          "`path` and `inverse` require a predicate, under which the matches are written"
+        );
+      }
+
+      for alias in &field.aliases {
+        self.validate_iri(alias);
+      }
+      if let Some(alias) = field.aliases.first().filter(|_| field.predicate.is_none()) {
+        emit_error!(alias, "`alias` requires a predicate");
+      }
+
+      if let Some(bind) = &field.bind {
+        self.validate_bind(field, bind);
+      }
+
+      if let Some((_, path)) = &field.aggregate {
+        if Path::parse(&path.value(), &self.prefixes).is_none() {
+          self.validate_iri(path);
+        }
+
+        if field.path.is_some() || field.bind.is_some() || field.flatten || field.id {
+          emit_error!(path, "an aggregate field only accepts a predicate");
+        }
+      }
+
+      if let Some(inverse) = &field.inverse {
+        self.validate_iri(inverse);
+      }
+    }
+  }
+
+  fn validate_bind(&self, field: &FieldOptions, bind: &LitStr) {
+    if field.path.is_some() || field.flatten || field.id {
+      emit_error!(bind, "`bind` only accepts a predicate");
+    }
+    if field.predicate.is_none() {
+      emit_error!(
+        bind,
+        "`bind` requires a predicate, under which the value is written"
+      );
+    }
+
+    expression::validate(bind, &self.prefixes);
+
+    for name in expression::variables(&bind.value()) {
+      let sibling = self
+        .fields
+        .iter()
+        .find(|sibling| sibling.name().as_ref() == Some(&name));
+
+      match sibling {
+        None => emit_error!(bind, "unknown field `{}`", name),
+        Some(sibling) if sibling.id => {}
+        Some(sibling)
+          if sibling.predicate.is_none()
+            || sibling.path.is_some()
+            || sibling.bind.is_some()
+            || sibling.aggregate.is_some()
+            || sibling.ignore =>
+        {
+          emit_error!(
+            bind,
+            "`{}` must be an `id` field or have a single predicate",
+            name
+          )
+        }
+        Some(_) => {}
+      }
+    }
+  }
+
+  fn validate_iri(&self, iri: &LitStr) {
+    let value = iri.value();
+
+    // As in `linked-data-core` and `linked_data_next`, an undeclared prefix is
+    // read as the scheme of an absolute IRI, so only a prefix that cannot be
+    // a scheme is known to be undeclared.
+    if let Some((prefix, _)) = value.split_once(':') {
+      let declared = self.prefixes.iter().any(|(name, _)| name == prefix);
+
+      if !declared && !is_scheme(prefix) {
+        emit_error!(iri, "undeclared prefix `{}`", prefix);
+        return;
+      }
+    }
+
+    let expanded = self.expand(iri);
+    if Iri::new(&expanded).is_err() {
+      emit_error!(iri, "invalid IRI `{}`", expanded);
+    }
+  }
+
+  /// Expands `iri` with the declared prefixes.
+  pub fn expand(&self, iri: &LitStr) -> String {
+    expand(iri, &self.prefixes)
+  }
+
+  /// Records the prefixes and the type of an `#[ld(...)]` item.
+  fn parse_ld_item(&mut self, item: &TokenStream) {
+    if let Ok(prefixes) = (|input: ParseStream| {
+      let name = input.parse::<syn::Ident>()?;
+      if name != "prefix" {
+        return Err(input.error("expected `prefix`"));
+      }
+      let content;
+      syn::parenthesized!(content in input);
+      content.parse_terminated(
+        |input| {
+          let name = input.parse::<LitStr>()?;
+          input.parse::<Token![=]>()?;
+          Ok((name.value(), input.parse::<LitStr>()?))
+        },
+        Token![,],
+      )
+    })
+    .parse2(item.clone())
+    {
+      self.prefixes.extend(prefixes);
+    } else if let Ok(type_iri) = parse_type(item) {
+      self.types.push(type_iri);
+    } else if parse_types(item).is_ok() {
+      emit_error!(
+        item,
+        "`type = [...]` is a `sparql` option: use `#[sparql(type = [...])]`"
+      );
+    } else {
+      reject_sparql_option(item, &CONTAINER_OPTIONS);
+    }
+  }
+
+  /// Records a `#[sparql(...)]` option of the container.
+  fn parse_item(&mut self, item: &TokenStream) {
+    if let Ok(path) = syn::parse2::<syn::Path>(item.clone()) {
+      if path.is_ident("transparent") {
+        self.transparent = true;
+        return;
+      }
+      if path.is_ident("property_paths") {
+        self.property_paths = true;
+        return;
+      }
+    } else if let Ok(type_iri) = parse_type(item) {
+      self.types.push(type_iri);
+      return;
+    } else if let Ok(types) = parse_types(item) {
+      self.types.extend(types);
+      return;
+    } else if let Some(meta) = syn::parse2::<MetaNameValue>(item.clone())
+      .ok()
+      .filter(|meta| meta.path.is_ident("type_match"))
+    {
+      if let Some(type_match) = string_value(&meta) {
+        match type_match.value().as_str() {
+          "exact" => self.match_subclasses = false,
+          "subclass" => self.match_subclasses = true,
+          _ => emit_error!(type_match, "expected `\"exact\"` or `\"subclass\"`"),
+        }
+      }
+      return;
+    }
+
+    emit_error!(item, "unknown `sparql` option");
+  }
+
+  pub fn field(&self, index: usize) -> FieldOptions {
+    self.fields.get(index).cloned().unwrap_or_default()
+  }
+
+  pub fn variant(&self, index: usize) -> FieldOptions {
+    self.variants.get(index).cloned().unwrap_or_default()
+  }
+}
+
+impl FieldOptions {
+  /// The name of the field, as written in `bind` expressions.
+  pub fn name(&self) -> Option<String> {
+    self
+      .ident
+      .as_ref()
+      .map(|ident| syn::ext::IdentExt::unraw(ident).to_string())
+  }
+
+  /// Whether the pattern of the field is built by the `ToConstructQuery`
+  /// implementation of its type.
+  fn is_pattern(&self) -> bool {
+    !self.ignore && self.sparql_with.is_none()
+  }
+
+  fn parse(attributes: &[Attribute], prefixes: &[(String, LitStr)]) -> Self {
+    let mut options = Self::default();
+    for_each_item(attributes, "ld", |item| options.parse_ld_item(item));
+    for_each_item(attributes, "sparql", |item| {
+      options.parse_item(item, prefixes)
+    });
+
+    if let Some(inverse) = &options.inverse {
+      if options.path.is_some() || !options.aliases.is_empty() {
+        emit_error!(
+          inverse,
+          "an `inverse` field cannot have a `path` or an `alias`"
+        );
+      }
+
+      let path = Path::Iri(expand(inverse, prefixes));
+      options.path = Some(Path::Inverse(Box::new(path)));
+    }
+
+    if let Some(predicate) = options
+      .predicate
+      .as_ref()
+      .filter(|_| !options.aliases.is_empty())
+    {
+      let main = options
+        .path
+        .take()
+        .unwrap_or_else(|| Path::Iri(expand(predicate, prefixes)));
+      let path = options.aliases.iter().fold(main, |path, alias| {
+        Path::Alternative(Box::new(path), Box::new(Path::Iri(expand(alias, prefixes))))
+      });
+      options.path = Some(path);
+    }
+
+    options
+  }
+
+  /// Records the predicate, `id`, `flatten` or `ignore` of an `#[ld(...)]`
+  /// item.
+  fn parse_ld_item(&mut self, item: &TokenStream) {
+    if let Ok(predicate) = syn::parse2::<LitStr>(item.clone()) {
+      self.predicate = Some(predicate);
+    } else if let Ok(path) = syn::parse2::<syn::Path>(item.clone()) {
+      self.id |= path.is_ident("id");
+      self.flatten |= path.is_ident("flatten");
+      self.ignore |= path.is_ident("ignore");
+    } else {
+      reject_sparql_option(item, &FIELD_OPTIONS);
+    }
+  }
+
+  /// Records a `#[sparql(...)]` option of the field.
+  fn parse_item(&mut self, item: &TokenStream, prefixes: &[(String, LitStr)]) {
+    if let Ok((function, path)) = parse_distinct_aggregate(item) {
+      self.aggregate = Some((function, path));
+      self.distinct = true;
+      return;
+    }
+
+    let Ok(meta) = syn::parse2::<MetaNameValue>(item.clone()) else {
+      emit_error!(item, "unknown `sparql` option");
+      return;
+    };
+
+    if meta.path.is_ident("path") {
+      if let Some(path) = string_value(&meta) {
+        match Path::parse(&path.value(), prefixes) {
+          Some(parsed) => self.path = Some(parsed),
+          None => emit_error!(path, "invalid property path `{}`", path.value()),
+        }
+      }
+    } else if meta.path.is_ident("lang") {
+      self.languages.extend(string_value(&meta));
+    } else if AGGREGATES
+      .iter()
+      .any(|aggregate| meta.path.is_ident(aggregate))
+    {
+      self.aggregate = string_value(&meta).map(|value| (meta.path.clone(), value));
+    } else if meta.path.is_ident("bind") {
+      self.bind = string_value(&meta);
+    } else if meta.path.is_ident("alias") {
+      self.aliases.extend(string_value(&meta));
+    } else if meta.path.is_ident("inverse") {
+      self.inverse = string_value(&meta);
+    } else if meta.path.is_ident("sparql_with") {
+      if let Some(path) = string_value(&meta) {
+        match path.parse() {
+          Ok(parsed) => self.sparql_with = Some(parsed),
+          Err(error) => emit_error!(path, "expected a path to a function: {}", error),
+        }
+      }
+    } else {
+      emit_error!(meta.path, "unknown `sparql` option");
+    }
+  }
+}
+
+/// Calls `parse_item` on the items of the `#[<name>(...)]` attributes.
+fn for_each_item(attributes: &[Attribute], name: &str, mut parse_item: impl FnMut(&TokenStream)) {
+  for attribute in attributes {
+    let syn::Meta::List(list) = &attribute.meta else {
+      continue;
+    };
+
+    if list.path.is_ident(name) {
+      split_items(list.tokens.clone())
+        .iter()
+        .for_each(&mut parse_item);
+    }
+  }
+}
+
+/// Reports an `#[ld(...)]` item naming one of the `#[sparql(...)]`
+/// `options`, which `linked-data` would otherwise ignore.
+fn reject_sparql_option(item: &TokenStream, options: &[&str]) {
+  let Ok(meta) = syn::parse2::<syn::Meta>(item.clone()) else {
+    return;
+  };
+
+  if let Some(name) = options.iter().find(|option| meta.path().is_ident(option)) {
+    match meta {
+      syn::Meta::Path(_) => emit_error!(
+        meta.path(),
+        "`{}` is a `sparql` option: use `#[sparql({})]`",
+        name,
+        name
+      ),
+      _ => emit_error!(
+        meta.path(),
+        "`{}` is a `sparql` option: use `#[sparql({} = ...)]`",
+        name,
+        name
+      ),
+    }
+  }
+}
+
+/// Parses a `count(distinct) = "..."` item, or one of the other aggregates.
+fn parse_distinct_aggregate(item: &TokenStream) -> syn::Result<(syn::Path, LitStr)> {
+  (|input: ParseStream| {
+    let function = input.parse::<syn::Path>()?;
+    if !AGGREGATES
+      .iter()
+      .any(|aggregate| function.is_ident(aggregate))
+    {
+      return Err(input.error("expected an aggregate function"));
+    }
+
+    let content;
+    syn::parenthesized!(content in input);
+    let distinct = content.parse::<syn::Ident>()?;
+    if distinct != "distinct" {
+      return Err(syn::Error::new(distinct.span(), "expected `distinct`"));
+    }
+
+    input.parse::<Token![=]>()?;
+    Ok((function, input.parse::<LitStr>()?))
+  })
+  .parse2(item.clone())
+}
+
+/// Parses a `type = ["...", ...]` item.
+fn parse_types(item: &TokenStream) -> syn::Result<Punctuated<LitStr, Token![,]>> {
+  (|input: ParseStream| {
+    input.parse::<Token![type]>()?;
+    input.parse::<Token![=]>()?;
+    let content;
+    syn::bracketed!(content in input);
+    content.parse_terminated(|input| input.parse::<LitStr>(), Token![,])
+  })
+  .parse2(item.clone())
+}
+
+/// Parses a `type = "..."` item.
+fn parse_type(item: &TokenStream) -> syn::Result<LitStr> {
+  (|input: ParseStream| {
+    input.parse::<Token![type]>()?;
+    input.parse::<Token![=]>()?;
+    input.parse::<LitStr>()
+  })
+  .parse2(item.clone())
+}
+
+/// Whether `prefix` is a valid IRI scheme: a letter followed by letters,
+/// digits, `+`, `-` or `.`.
+fn is_scheme(prefix: &str) -> bool {
+  let mut chars = prefix.chars();
+  chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+    && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Expands `iri` when it is a compact IRI with a declared prefix.
+fn expand(iri: &LitStr, prefixes: &[(String, LitStr)]) -> String {
+  let value = iri.value();
+
+  value
+    .split_once(':')
+    .and_then(|(prefix, suffix)| {
+      prefixes
+        .iter()
+        .find(|(name, _)| name == prefix)
+        .map(|(_, namespace)| namespace.value() + suffix)
+    })
+    .unwrap_or(value)
+}
+
+/// The string literal value of `meta`, or `None` after reporting it.
+fn string_value(meta: &MetaNameValue) -> Option<LitStr> {
+  match &meta.value {
+    Expr::Lit(ExprLit {
+      lit: Lit::Str(value),
+      ..
+    }) => Some(value.clone()),
+    value => {
+      emit_error!(value, "expected a string literal");
+      None
+    }
+  }
+}
+
+/// Splits the content of an attribute on its top-level commas.
+fn split_items(tokens: TokenStream) -> Vec<TokenStream> {
+  let mut items = vec![TokenStream::new()];
+
+  for token in tokens {
+    match &token {
+      TokenTree::Punct(punct) if punct.as_char() == ',' => items.push(TokenStream::new()),
+      _ => items.last_mut().unwrap().extend([token]),
+    }
+  }
+
+  items.retain(|item| !item.is_empty());
+  items
+}
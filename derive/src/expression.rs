@@ -0,0 +1,279 @@
+use proc_macro_error::emit_error;
+use proc_macro2::TokenStream;
+use spargebra::Query;
+use spargebra::algebra::{Expression, Function, GraphPattern};
+use syn::LitStr;
+
+/// A part of a `bind` expression: plain text, or a `?field` variable naming a
+/// sibling field.
+#[derive(Clone, Debug, PartialEq)]
+enum Piece {
+  Text(String),
+  Variable(String),
+}
+
+/// Splits `expression` on its variables, leaving the string literals and the
+/// `<...>` IRIs as they are.
+fn split(expression: &str) -> Vec<Piece> {
+  let mut pieces = Vec::new();
+  let mut text = String::new();
+  let mut chars = expression.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    match c {
+      '"' | '\'' => {
+        text.push(c);
+
+        // `"""..."""` and `'''...'''` end on three quotes.
+        let mut rest = chars.clone();
+        let long = rest.next() == Some(c) && rest.next() == Some(c);
+        if long {
+          text.extend([c, c]);
+          chars.nth(1);
+        }
+
+        let mut quotes = 0;
+        while let Some(next) = chars.next() {
+          text.push(next);
+          if next == '\\' {
+            text.extend(chars.next());
+            quotes = 0;
+          } else if next == c {
+            quotes += 1;
+            if !long || quotes == 3 {
+              break;
+            }
+          } else {
+            quotes = 0;
+          }
+        }
+      }
+      '<' if is_iri(chars.clone()) => {
+        text.push(c);
+        for next in chars.by_ref() {
+          text.push(next);
+          if next == '>' {
+            break;
+          }
+        }
+      }
+      '?' | '$' if chars.peek().is_some_and(|next| is_name_char(*next)) => {
+        text.push('?');
+        pieces.push(Piece::Text(std::mem::take(&mut text)));
+
+        let mut name = String::new();
+        while let Some(next) = chars.next_if(|next| is_name_char(*next)) {
+          name.push(next);
+        }
+        pieces.push(Piece::Variable(name));
+      }
+      _ => text.push(c),
+    }
+  }
+
+  if !text.is_empty() {
+    pieces.push(Piece::Text(text));
+  }
+  pieces
+}
+
+/// Whether the characters following a `<` end an IRI, rather than a
+/// less-than comparison, as read by the SPARQL grammar.
+fn is_iri(mut chars: impl Iterator<Item = char>) -> bool {
+  chars
+    .find(|c| *c == '>' || c.is_whitespace() || "<\"{}|^`\\".contains(*c))
+    .is_some_and(|c| c == '>')
+}
+
+/// The variables of `expression`.
+pub fn variables(expression: &str) -> Vec<String> {
+  split(expression)
+    .into_iter()
+    .filter_map(|piece| match piece {
+      Piece::Variable(name) => Some(name),
+      Piece::Text(_) => None,
+    })
+    .collect()
+}
+
+/// Reports `expression` when it is not a valid SPARQL expression, or holds an
+/// `EXISTS` pattern.
+pub fn validate(expression: &LitStr, prefixes: &[(String, LitStr)]) {
+  if parse(expression, prefixes).is_some_and(|parsed| has_exists(&parsed)) {
+    emit_error!(expression, "`EXISTS` is not supported in `bind`");
+  }
+}
+
+/// Parses `expression`, whose compact IRIs use `prefixes`, reporting it when
+/// it is not a valid SPARQL expression.
+pub fn parse(expression: &LitStr, prefixes: &[(String, LitStr)]) -> Option<Expression> {
+  let prologue = prefixes
+    .iter()
+    .map(|(prefix, namespace)| format!("PREFIX {prefix}: <{}>\n", namespace.value()))
+    .collect::<String>();
+
+  let query = format!(
+    "{prologue}SELECT * WHERE {{ BIND(({}) AS ?value) }}",
+    expression.value()
+  );
+  let mut pattern = match Query::parse(&query, None) {
+    Ok(Query::Select { pattern, .. }) => pattern,
+    Ok(_) => return None,
+    Err(error) => {
+      emit_error!(expression, "invalid expression: {}", error);
+      return None;
+    }
+  };
+
+  loop {
+    match pattern {
+      GraphPattern::Extend { expression, .. } => return Some(expression),
+      GraphPattern::Project { inner, .. } => pattern = *inner,
+      _ => return None,
+    }
+  }
+}
+
+/// The tokens building `expression`, with the tokens of the variable named
+/// after each field given by `field_variable`.
+pub fn to_tokens(
+  expression: &Expression,
+  field_variable: &impl Fn(&str) -> Option<TokenStream>,
+) -> TokenStream {
+  let algebra = quote::quote! { ::linked_data_sparql::reexport::spargebra::algebra };
+  let term = quote::quote! { ::linked_data_sparql::reexport::spargebra::term };
+  let boxed = |expression: &Expression| {
+    let tokens = to_tokens(expression, field_variable);
+    quote::quote! { ::std::boxed::Box::new(#tokens) }
+  };
+  let list = |expressions: &[Expression]| {
+    let tokens = expressions
+      .iter()
+      .map(|expression| to_tokens(expression, field_variable));
+    quote::quote! { vec![#(#tokens),*] }
+  };
+  let binary = |operator: &str, left: &Expression, right: &Expression| {
+    let operator = syn::Ident::new(operator, proc_macro2::Span::call_site());
+    let (left, right) = (boxed(left), boxed(right));
+    quote::quote! { #algebra::Expression::#operator(#left, #right) }
+  };
+  let unary = |operator: &str, inner: &Expression| {
+    let operator = syn::Ident::new(operator, proc_macro2::Span::call_site());
+    let inner = boxed(inner);
+    quote::quote! { #algebra::Expression::#operator(#inner) }
+  };
+  let variable = |name: &str| {
+    field_variable(name).unwrap_or_else(|| quote::quote! { #term::Variable::new_unchecked(#name) })
+  };
+
+  match expression {
+    Expression::NamedNode(node) => {
+      let iri = node.as_str();
+      quote::quote! { #algebra::Expression::NamedNode(#term::NamedNode::new_unchecked(#iri)) }
+    }
+    Expression::Literal(literal) => {
+      let value = literal.value();
+      let literal = match literal.language() {
+        Some(language) => {
+          quote::quote! { #term::Literal::new_language_tagged_literal_unchecked(#value, #language) }
+        }
+        None => {
+          let datatype = literal.datatype().as_str();
+          quote::quote! {
+            #term::Literal::new_typed_literal(#value, #term::NamedNode::new_unchecked(#datatype))
+          }
+        }
+      };
+      quote::quote! { #algebra::Expression::Literal(#literal) }
+    }
+    Expression::Variable(name) => {
+      let variable = variable(name.as_str());
+      quote::quote! { #algebra::Expression::Variable(#variable) }
+    }
+    Expression::Bound(name) => {
+      let variable = variable(name.as_str());
+      quote::quote! { #algebra::Expression::Bound(#variable) }
+    }
+    Expression::Or(left, right) => binary("Or", left, right),
+    Expression::And(left, right) => binary("And", left, right),
+    Expression::Equal(left, right) => binary("Equal", left, right),
+    Expression::SameTerm(left, right) => binary("SameTerm", left, right),
+    Expression::Greater(left, right) => binary("Greater", left, right),
+    Expression::GreaterOrEqual(left, right) => binary("GreaterOrEqual", left, right),
+    Expression::Less(left, right) => binary("Less", left, right),
+    Expression::LessOrEqual(left, right) => binary("LessOrEqual", left, right),
+    Expression::Add(left, right) => binary("Add", left, right),
+    Expression::Subtract(left, right) => binary("Subtract", left, right),
+    Expression::Multiply(left, right) => binary("Multiply", left, right),
+    Expression::Divide(left, right) => binary("Divide", left, right),
+    Expression::UnaryPlus(inner) => unary("UnaryPlus", inner),
+    Expression::UnaryMinus(inner) => unary("UnaryMinus", inner),
+    Expression::Not(inner) => unary("Not", inner),
+    Expression::In(needle, haystack) => {
+      let (needle, haystack) = (boxed(needle), list(haystack));
+      quote::quote! { #algebra::Expression::In(#needle, #haystack) }
+    }
+    Expression::If(condition, then, otherwise) => {
+      let (condition, then, otherwise) = (boxed(condition), boxed(then), boxed(otherwise));
+      quote::quote! { #algebra::Expression::If(#condition, #then, #otherwise) }
+    }
+    Expression::Coalesce(expressions) => {
+      let expressions = list(expressions);
+      quote::quote! { #algebra::Expression::Coalesce(#expressions) }
+    }
+    Expression::FunctionCall(function, arguments) => {
+      let function = match function {
+        Function::Custom(node) => {
+          let iri = node.as_str();
+          quote::quote! { #algebra::Function::Custom(#term::NamedNode::new_unchecked(#iri)) }
+        }
+        // The name of a unit variant, such as `Concat`.
+        function => {
+          let name = syn::Ident::new(&format!("{function:?}"), proc_macro2::Span::call_site());
+          quote::quote! { #algebra::Function::#name }
+        }
+      };
+      let arguments = list(arguments);
+      quote::quote! { #algebra::Expression::FunctionCall(#function, #arguments) }
+    }
+    // Rejected by `validate`.
+    Expression::Exists(_) => TokenStream::new(),
+  }
+}
+
+/// Whether `expression` holds an `EXISTS` pattern, which has no tokens.
+fn has_exists(expression: &Expression) -> bool {
+  match expression {
+    Expression::Exists(_) => true,
+    Expression::NamedNode(_)
+    | Expression::Literal(_)
+    | Expression::Variable(_)
+    | Expression::Bound(_) => false,
+    Expression::Or(left, right)
+    | Expression::And(left, right)
+    | Expression::Equal(left, right)
+    | Expression::SameTerm(left, right)
+    | Expression::Greater(left, right)
+    | Expression::GreaterOrEqual(left, right)
+    | Expression::Less(left, right)
+    | Expression::LessOrEqual(left, right)
+    | Expression::Add(left, right)
+    | Expression::Subtract(left, right)
+    | Expression::Multiply(left, right)
+    | Expression::Divide(left, right) => has_exists(left) || has_exists(right),
+    Expression::UnaryPlus(inner) | Expression::UnaryMinus(inner) | Expression::Not(inner) => {
+      has_exists(inner)
+    }
+    Expression::In(needle, haystack) => has_exists(needle) || haystack.iter().any(has_exists),
+    Expression::If(condition, then, otherwise) => {
+      has_exists(condition) || has_exists(then) || has_exists(otherwise)
+    }
+    Expression::Coalesce(expressions) | Expression::FunctionCall(_, expressions) => {
+      expressions.iter().any(has_exists)
+    }
+  }
+}
+
+fn is_name_char(c: char) -> bool {
+  c.is_alphanumeric() || c == '_'
+}
@@ -1,86 +1,291 @@
+mod attributes;
+mod expression;
+mod path;
+
+use crate::attributes::{FieldOptions, Options};
+use crate::path::Path;
 use linked_data_core::{
   PredicatePath, RdfEnum, RdfField, RdfStruct, RdfType, RdfVariant, TokenGenerator,
 };
-use proc_macro_error::proc_macro_error;
-use proc_macro2::TokenStream;
-use quote::ToTokens;
-use syn::DeriveInput;
+use proc_macro_error::{abort, emit_error, proc_macro_error};
+use proc_macro2::{Ident, TokenStream};
+use syn::visit::{self, Visit};
+use syn::{Data, DeriveInput, Fields, Generics, PathSegment, Type};
 
 #[proc_macro_error]
-#[proc_macro_derive(Sparql, attributes(ld))]
+#[proc_macro_derive(Sparql, attributes(ld, sparql))]
 pub fn derive_serialize(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
   let raw_input = syn::parse_macro_input!(item as DeriveInput);
+  let options = Options::parse(&raw_input);
+  options.validate();
+  proc_macro_error::abort_if_dirty();
+
+  if options.transparent {
+    return generate_transparent(&raw_input, &options).into();
+  }
+
+  if !is_supported_by_core(&raw_input) {
+    return generate_from_input(&raw_input, &options).into();
+  }
+
   let linked_data_type: RdfType<Sparql> = RdfType::from_derive(raw_input);
 
   let mut output = TokenStream::new();
-  linked_data_type.to_tokens(&mut output);
+  Sparql::generate_type(&linked_data_type, &options, &mut output);
   output.into()
 }
 
+/// Delegates to the pattern of the single field of a newtype struct.
+fn generate_transparent(input: &DeriveInput, options: &Options) -> TokenStream {
+  let ident = &input.ident;
+  let generics = bounded_generics(options);
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+  let Data::Struct(data) = &input.data else {
+    abort!(ident, "`transparent` is only supported on structs");
+  };
+  let mut fields = data.fields.iter();
+  let (Some(field), None) = (fields.next(), fields.next()) else {
+    abort!(ident, "`transparent` requires exactly one field");
+  };
+  let ty = &field.ty;
+
+  quote::quote! {
+    impl #impl_generics ::linked_data_sparql::ToConstructQuery for #ident #ty_generics #where_clause {
+      fn to_query_with_binding(binding_variable: ::linked_data_sparql::reexport::spargebra::term::Variable) -> ::linked_data_sparql::ConstructQuery {
+        <#ty as ::linked_data_sparql::ToConstructQuery>::to_query_with_binding(binding_variable)
+      }
+    }
+  }
+}
+
+/// `generics` with a `ToConstructQuery` bound on the type parameters found in
+/// the types of the fields matched by the pattern, so that a parameter only
+/// used by an ignored field, such as in `PhantomData<T>`, stays unbounded.
+fn bounded_generics(options: &Options) -> Generics {
+  struct Visitor<'a> {
+    used: Vec<&'a Ident>,
+    params: Vec<&'a Ident>,
+  }
+
+  impl<'ast> Visit<'ast> for Visitor<'ast> {
+    fn visit_path_segment(&mut self, segment: &'ast PathSegment) {
+      if self.params.contains(&&segment.ident) {
+        self.used.push(&segment.ident);
+      }
+      visit::visit_path_segment(self, segment);
+    }
+  }
+
+  let mut visitor = Visitor {
+    used: Vec::new(),
+    params: options
+      .generics
+      .type_params()
+      .map(|param| &param.ident)
+      .collect(),
+  };
+  for ty in &options.pattern_types {
+    visitor.visit_type(ty);
+  }
+  let used = visitor.used;
+
+  let mut generics = options.generics.clone();
+  for param in generics.type_params_mut() {
+    if used.contains(&&param.ident) {
+      param
+        .bounds
+        .push(syn::parse_quote!(::linked_data_sparql::ToConstructQuery));
+    }
+  }
+  generics
+}
+
 struct Sparql;
 
+/// Generation without the SPARQL options; the derive goes through the
+/// [`Sparql`] methods taking [`Options`] instead.
 impl TokenGenerator for Sparql {
   fn generate_type_tokens(linked_data_type: &RdfType<Self>, tokens: &mut TokenStream) {
+    Self::generate_type(linked_data_type, &Options::default(), tokens)
+  }
+
+  fn generate_struct_tokens(rdf_struct: &RdfStruct<Self>, tokens: &mut TokenStream) {
+    Self::generate_struct(rdf_struct, &Options::default(), tokens)
+  }
+
+  fn generate_enum_tokens(r#enum: &RdfEnum<Self>, tokens: &mut TokenStream) {
+    Self::generate_enum(r#enum, &Options::default(), tokens)
+  }
+
+  fn generate_variant_tokens(variant: &RdfVariant<Self>, tokens: &mut TokenStream) {
+    Self::generate_variant(variant, &FieldOptions::default(), false, tokens)
+  }
+
+  fn generate_field_tokens(field: &RdfField<Self>, tokens: &mut TokenStream) {
+    Self::generate_field(field, &FieldOptions::default(), tokens)
+  }
+}
+
+impl Sparql {
+  fn generate_type(linked_data_type: &RdfType<Self>, options: &Options, tokens: &mut TokenStream) {
     tokens.extend(quote::quote! {
         use ::linked_data_sparql::Join as _;
     });
 
-    let implementations = match linked_data_type {
-      RdfType::Enum(rdf_enum) => quote::quote! {#rdf_enum},
-      RdfType::Struct(rdf_struct) => quote::quote! {#rdf_struct},
-    };
-
-    tokens.extend(implementations)
+    match linked_data_type {
+      RdfType::Enum(rdf_enum) => Self::generate_enum(rdf_enum, options, tokens),
+      RdfType::Struct(rdf_struct) => Self::generate_struct(rdf_struct, options, tokens),
+    }
   }
 
-  fn generate_struct_tokens(rdf_struct: &RdfStruct<Self>, tokens: &mut TokenStream) {
+  fn generate_struct(rdf_struct: &RdfStruct<Self>, options: &Options, tokens: &mut TokenStream) {
     let ident = &rdf_struct.ident;
-    let fields = &rdf_struct.fields;
+    let generics = bounded_generics(options);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut fields = TokenStream::new();
+    let mut computed = TokenStream::new();
+    for (index, field) in rdf_struct.fields.iter().enumerate() {
+      let field_options = options.field(index);
 
-    let type_tokens =
-      rdf_struct.type_iri().map(|iri| iri.clone().into_string())
-        .map(|type_iri| {
-          quote::quote! {
-            .join_with(
-              binding_variable.clone(),
-              ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
-              ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#type_iri),
-            )
-          }
-        })
-        .unwrap_or_default();
+      // Computed last, once the fields they refer to are bound.
+      if field_options.bind.is_some() {
+        computed.extend(generate_bind(&field.ty, &field_options, options));
+        continue;
+      }
+      if field_options.aggregate.is_some() {
+        computed.extend(generate_aggregate(&field.ty, &field_options, options));
+        continue;
+      }
+
+      Self::generate_field(field, &field_options, &mut fields);
+    }
+
+    let type_tokens = if options.types.is_empty() {
+      let type_iris = rdf_struct.type_iri().map(|iri| iri.clone().into_string());
+      generate_types(type_iris, options.match_subclasses)
+    } else {
+      generate_types(
+        options
+          .types
+          .iter()
+          .map(|type_iri| options.expand(type_iri)),
+        options.match_subclasses,
+      )
+    };
+
+    let id_tokens = if options.fields.iter().any(|field| field.id) {
+      quote::quote! { .require_iri(binding_variable.clone()) }
+    } else {
+      TokenStream::new()
+    };
+
+    let variables = options
+      .fields
+      .iter()
+      .filter(|field| field.referenced && !field.id)
+      .filter_map(variable);
 
     tokens.extend(quote::quote! {
-      impl ::linked_data_sparql::ToConstructQuery for #ident {
+      impl #impl_generics ::linked_data_sparql::ToConstructQuery for #ident #ty_generics #where_clause {
         fn to_query_with_binding(binding_variable: ::linked_data_sparql::reexport::spargebra::term::Variable) -> ::linked_data_sparql::ConstructQuery {
-          ::linked_data_sparql::ConstructQuery::default()
-          #(#fields)*
-          #type_tokens
+          ::linked_data_sparql::expand_once::<Self>(binding_variable, move |binding_variable| {
+            #(let #variables = ::linked_data_sparql::reexport::spargebra::term::Variable::new_unchecked(::linked_data_sparql::reexport::spargebra::term::BlankNode::default().into_string());)*
+
+            ::linked_data_sparql::ConstructQuery::default()
+            #fields
+            #computed
+            #type_tokens
+            #id_tokens
+          })
         }
       }
     });
   }
 
-  fn generate_enum_tokens(r#enum: &RdfEnum<Self>, tokens: &mut TokenStream) {
-    let variants = &r#enum.variants;
+  fn generate_enum(r#enum: &RdfEnum<Self>, options: &Options, tokens: &mut TokenStream) {
     let ident = &r#enum.ident;
+    let generics = bounded_generics(options);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut variants = TokenStream::new();
+    for (index, variant) in r#enum.variants.iter().enumerate() {
+      Self::generate_variant(
+        variant,
+        &options.variant(index),
+        options.property_paths,
+        &mut variants,
+      );
+    }
+
+    if options
+      .variants
+      .iter()
+      .any(|variant| !variant.languages.is_empty())
+    {
+      tokens.extend(quote::quote! {
+          use ::linked_data_sparql::Union as _;
+      });
+    }
 
     tokens.extend(quote::quote! {
-      impl ::linked_data_sparql::ToConstructQuery for #ident {
+      impl #impl_generics ::linked_data_sparql::ToConstructQuery for #ident #ty_generics #where_clause {
         fn to_query_with_binding(binding_variable: ::linked_data_sparql::reexport::spargebra::term::Variable) -> ::linked_data_sparql::ConstructQuery {
-          ::linked_data_sparql::ConstructQuery::default()
-          #(#variants)*
+          ::linked_data_sparql::expand_once::<Self>(binding_variable, move |binding_variable| {
+            ::linked_data_sparql::ConstructQuery::default()
+            #variants
+          })
         }
       }
     });
   }
 
-  fn generate_variant_tokens(variant: &RdfVariant<Self>, tokens: &mut TokenStream) {
-    let ty = &variant.ty;
-    let inner_generator = quote::quote! { #ty::to_query_with_binding };
+  fn generate_variant(
+    variant: &RdfVariant<Self>,
+    options: &FieldOptions,
+    property_paths: bool,
+    tokens: &mut TokenStream,
+  ) {
+    let inner_generator = generator(&variant.ty, options);
+
+    if let Some(path) = &options.path {
+      let PredicatePath::Predicate(predicate) = variant.predicate_path() else {
+        emit_error!(
+          proc_macro2::Span::call_site(),
+          "`path` requires a single variant predicate"
+        );
+        return;
+      };
+      let predicate_iri = predicate.as_str();
+      tokens.extend(quote::quote! {
+        .union_with_path(
+          binding_variable.clone(),
+          ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#predicate_iri),
+          #path,
+          #inner_generator,
+        )
+      });
+      return;
+    }
 
     let (iri_str, predicate_generator) = match &variant.predicate_path() {
       PredicatePath::Predicate(iri) => (iri.as_str(), inner_generator),
+      PredicatePath::ChainedPath {
+        to_blank,
+        from_blank,
+      } if property_paths => {
+        let (from_blank_str, to_blank_str) = (from_blank.as_str(), to_blank.as_str());
+        tokens.extend(quote::quote! {
+          .union_with_chain(
+            binding_variable.clone(),
+            vec![
+              ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#from_blank_str),
+              ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#to_blank_str),
+            ],
+            #inner_generator,
+          )
+        });
+        return;
+      }
       PredicatePath::ChainedPath {
         to_blank,
         from_blank,
@@ -99,37 +304,422 @@ impl TokenGenerator for Sparql {
       }
     };
 
-    tokens.extend(quote::quote! {
-      .union_with_binding(
-        binding_variable.clone(),
-        ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#iri_str),
-        #predicate_generator
-      )
-    });
+    if options.languages.is_empty() {
+      tokens.extend(quote::quote! {
+        .union_with_binding(
+          binding_variable.clone(),
+          ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#iri_str),
+          #predicate_generator
+        )
+      });
+    } else {
+      let languages = &options.languages;
+      tokens.extend(quote::quote! {
+        .union(
+          ::linked_data_sparql::ConstructQuery::new_with_binding(
+            binding_variable.clone(),
+            ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#iri_str),
+            #predicate_generator
+          )
+          .prefer_languages(&[#(#languages),*])
+        )
+      });
+    }
   }
 
-  fn generate_field_tokens(field: &RdfField<Self>, tokens: &mut TokenStream) {
+  fn generate_field(
+    field: &RdfField<Self>,
+    field_options: &FieldOptions,
+    tokens: &mut TokenStream,
+  ) {
     if field.is_ignored() {
       return;
     }
 
-    if field.is_flattened() {
-      let ty = &field.ty;
+    let predicate = field
+      .predicate()
+      .map(|predicate| predicate.as_str().to_owned());
+    generate_field_pattern(
+      &field.ty,
+      predicate.as_deref(),
+      field.is_flattened(),
+      field_options,
+      tokens,
+    );
+  }
+}
+
+/// Joins the pattern of a field of type `ty` on `binding_variable`.
+fn generate_field_pattern(
+  ty: &Type,
+  predicate: Option<&str>,
+  flatten: bool,
+  options: &FieldOptions,
+  tokens: &mut TokenStream,
+) {
+  let generator = generator(ty, options);
+
+  if flatten {
+    tokens.extend(quote::quote! {
+      .join(#generator(binding_variable.clone()))
+    });
+  }
+
+  if let Some(path) = &options.path {
+    if let Some(predicate_iri) = predicate {
       tokens.extend(quote::quote! {
-        .join(#ty::to_query_with_binding(binding_variable.clone()))
+        .join_with_path(
+          binding_variable.clone(),
+          ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#predicate_iri),
+          #path,
+          #generator,
+        )
       });
     }
+  } else if let Some(predicate_iri) = predicate {
+    // The value of a field used in a `bind` expression is bound to its own
+    // variable.
+    let (join, new, object) = match variable(options).filter(|_| options.referenced) {
+      Some(object) => (
+        quote::quote! { join_with_object },
+        quote::quote! { new_with_object },
+        quote::quote! { #object.clone(), },
+      ),
+      None => (
+        quote::quote! { join_with_binding },
+        quote::quote! { new_with_binding },
+        TokenStream::new(),
+      ),
+    };
 
-    if let Some(predicate) = field.predicate() {
-      let ty = &field.ty;
-      let predicate_iri = predicate.as_str();
+    if options.languages.is_empty() {
       tokens.extend(quote::quote! {
-        .join_with_binding(
+        .#join(
           binding_variable.clone(),
           ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#predicate_iri),
-          #ty::to_query_with_binding,
+          #object
+          #generator,
         )
       });
+    } else {
+      let languages = &options.languages;
+      tokens.extend(quote::quote! {
+        .join(
+          ::linked_data_sparql::ConstructQuery::#new(
+            binding_variable.clone(),
+            ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#predicate_iri),
+            #object
+            #generator,
+          )
+          .prefer_languages(&[#(#languages),*])
+        )
+      });
+    }
+  }
+}
+
+/// The variable bound to the value of a field used in `bind` expressions.
+fn variable(options: &FieldOptions) -> Option<Ident> {
+  let name = options.name()?;
+  Some(quote::format_ident!("__{}", name))
+}
+
+/// Joins the value of the `bind` expression of a field of type `ty`, under
+/// its predicate.
+fn generate_bind(ty: &Type, field_options: &FieldOptions, options: &Options) -> TokenStream {
+  let Some(bind) = &field_options.bind else {
+    return TokenStream::new();
+  };
+
+  let Some(predicate) = &field_options.predicate else {
+    return TokenStream::new();
+  };
+  let predicate_iri = options.expand(predicate);
+
+  let Some(expression) = expression::parse(bind, &options.prefixes) else {
+    return TokenStream::new();
+  };
+  let expression = expression::to_tokens(&expression, &|name| {
+    let sibling = options
+      .fields
+      .iter()
+      .find(|sibling| sibling.name().as_deref() == Some(name))?;
+    if sibling.id {
+      return Some(quote::quote! { binding_variable.clone() });
     }
+    let variable = variable(sibling)?;
+    Some(quote::quote! { #variable.clone() })
+  });
+  let generator = generator(ty, field_options);
+
+  quote::quote! {
+    .join_with_expression(
+      binding_variable.clone(),
+      ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#predicate_iri),
+      #expression,
+      #generator,
+    )
+  }
+}
+
+/// Joins the aggregate of a field of type `ty`, under its predicate or a
+/// synthetic one named after the field.
+fn generate_aggregate(ty: &Type, field_options: &FieldOptions, options: &Options) -> TokenStream {
+  let Some((function, path)) = &field_options.aggregate else {
+    return TokenStream::new();
+  };
+
+  let predicate_iri = match &field_options.predicate {
+    Some(predicate) => options.expand(predicate),
+    None => format!(
+      "urn:linked-data-sparql:aggregate:{}",
+      field_options.name().unwrap_or_default()
+    ),
+  };
+
+  let path = Path::parse(&path.value(), &options.prefixes)
+    .unwrap_or_else(|| Path::Iri(options.expand(path)));
+  let function = match function.get_ident().map(ToString::to_string).as_deref() {
+    Some("count") => quote::quote! { Count },
+    Some("sum") => quote::quote! { Sum },
+    Some("min") => quote::quote! { Min },
+    Some("max") => quote::quote! { Max },
+    Some("sample") => quote::quote! { Sample },
+    _ => unreachable!("only aggregate functions are parsed as aggregates"),
+  };
+  let distinct = field_options.distinct;
+  let generator = generator(ty, field_options);
+
+  quote::quote! {
+    .join_with_aggregate(
+      binding_variable.clone(),
+      ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#predicate_iri),
+      #path,
+      ::linked_data_sparql::reexport::spargebra::algebra::AggregateFunction::#function,
+      #distinct,
+      #generator,
+    )
+  }
+}
+
+/// Requires `binding_variable` to have each of `type_iris`, or one of their
+/// subclasses with `match_subclasses`.
+fn generate_types(
+  type_iris: impl IntoIterator<Item = String>,
+  match_subclasses: bool,
+) -> TokenStream {
+  type_iris
+    .into_iter()
+    .map(|type_iri| {
+      if match_subclasses {
+        quote::quote! {
+          .join_with_subclass_of(
+            binding_variable.clone(),
+            ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#type_iri),
+          )
+        }
+      } else {
+        quote::quote! {
+          .join_with(
+            binding_variable.clone(),
+            ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+            ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#type_iri),
+          )
+        }
+      }
+    })
+    .collect()
+}
+
+/// The function building the pattern of a value of type `ty`.
+fn generator(ty: &Type, options: &FieldOptions) -> TokenStream {
+  match &options.sparql_with {
+    Some(path) => quote::quote! { #path },
+    None => {
+      quote::quote! { <#ty as ::linked_data_sparql::ToConstructQuery>::to_query_with_binding }
+    }
+  }
+}
+
+/// Whether `linked-data-core` models `input`: structs with named fields, and
+/// enums whose variants all hold a single unnamed field.
+fn is_supported_by_core(input: &DeriveInput) -> bool {
+  match &input.data {
+    Data::Struct(data) => matches!(data.fields, Fields::Named(_)),
+    Data::Enum(data) => data.variants.iter().all(
+      |variant| matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1),
+    ),
+    Data::Union(_) => true,
   }
 }
+
+/// Generation for tuple structs and for enums with struct-like or
+/// multi-field variants, straight from the input.
+fn generate_from_input(input: &DeriveInput, options: &Options) -> TokenStream {
+  let ident = &input.ident;
+  let generics = bounded_generics(options);
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+  let body = match &input.data {
+    Data::Struct(data) => {
+      let fields = generate_fields(&data.fields, &options.fields, options);
+
+      let type_tokens = generate_types(
+        options
+          .types
+          .iter()
+          .map(|type_iri| options.expand(type_iri)),
+        options.match_subclasses,
+      );
+
+      let id_tokens = options
+        .fields
+        .iter()
+        .any(|field| field.id)
+        .then(|| quote::quote! { .require_iri(binding_variable.clone()) });
+
+      quote::quote! {
+        ::linked_data_sparql::ConstructQuery::default()
+        #fields
+        #type_tokens
+        #id_tokens
+      }
+    }
+    Data::Enum(data) => {
+      let variants = data
+        .variants
+        .iter()
+        .zip(&options.variants)
+        .zip(&options.variant_fields)
+        .map(|((variant, variant_options), field_options)| {
+          generate_variant_from_input(&variant.fields, variant_options, field_options, options)
+        });
+
+      quote::quote! {
+        ::linked_data_sparql::ConstructQuery::default()
+        #(#variants)*
+      }
+    }
+    Data::Union(data) => abort!(data.union_token, "unions are not supported"),
+  };
+
+  quote::quote! {
+    use ::linked_data_sparql::Join as _;
+    use ::linked_data_sparql::Union as _;
+
+    impl #impl_generics ::linked_data_sparql::ToConstructQuery for #ident #ty_generics #where_clause {
+      fn to_query_with_binding(binding_variable: ::linked_data_sparql::reexport::spargebra::term::Variable) -> ::linked_data_sparql::ConstructQuery {
+        ::linked_data_sparql::expand_once::<Self>(binding_variable, move |binding_variable| {
+          #body
+        })
+      }
+    }
+  }
+}
+
+/// A union branch for a variant: its fields are joined on the node linked
+/// by the variant predicate, or on the resource itself without one. A single
+/// field without a predicate is the node itself.
+fn generate_variant_from_input(
+  fields: &Fields,
+  variant_options: &FieldOptions,
+  field_options: &[FieldOptions],
+  options: &Options,
+) -> TokenStream {
+  let generator = match (fields.iter().next(), field_options) {
+    (Some(field), [field_options])
+      if field_options.predicate.is_none() && !field_options.flatten =>
+    {
+      if field_options.sparql_with.is_some() {
+        generator(&field.ty, field_options)
+      } else {
+        generator(&field.ty, variant_options)
+      }
+    }
+    _ => {
+      let fields = generate_fields(fields, field_options, options);
+      quote::quote! {
+        |binding_variable: ::linked_data_sparql::reexport::spargebra::term::Variable| {
+          ::linked_data_sparql::ConstructQuery::default()
+          #fields
+        }
+      }
+    }
+  };
+
+  let Some(predicate) = &variant_options.predicate else {
+    return quote::quote! {
+      .union((#generator)(binding_variable.clone()))
+    };
+  };
+
+  let predicate_iri = options.expand(predicate);
+  if let Some(path) = &variant_options.path {
+    return quote::quote! {
+      .union(::linked_data_sparql::ConstructQuery::new_with_path(
+        binding_variable.clone(),
+        ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#predicate_iri),
+        #path,
+        #generator,
+      ))
+    };
+  }
+  let languages = (!variant_options.languages.is_empty()).then(|| {
+    let languages = &variant_options.languages;
+    quote::quote! { .prefer_languages(&[#(#languages),*]) }
+  });
+
+  quote::quote! {
+    .union(
+      ::linked_data_sparql::ConstructQuery::new_with_binding(
+        binding_variable.clone(),
+        ::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#predicate_iri),
+        #generator,
+      )
+      #languages
+    )
+  }
+}
+
+fn generate_fields(
+  fields: &Fields,
+  field_options: &[FieldOptions],
+  options: &Options,
+) -> TokenStream {
+  let mut tokens = TokenStream::new();
+
+  for (field, field_options) in fields.iter().zip(field_options) {
+    if field_options.ignore {
+      continue;
+    }
+
+    if let Some(bind) = &field_options.bind {
+      emit_error!(
+        bind,
+        "`bind` is only supported on structs with named fields"
+      );
+      continue;
+    }
+    if let Some((function, _)) = &field_options.aggregate {
+      emit_error!(
+        function,
+        "aggregate fields are only supported on structs with named fields"
+      );
+      continue;
+    }
+
+    let predicate = field_options
+      .predicate
+      .as_ref()
+      .map(|predicate| options.expand(predicate));
+    generate_field_pattern(
+      &field.ty,
+      predicate.as_deref(),
+      field_options.flatten,
+      field_options,
+      &mut tokens,
+    );
+  }
+
+  tokens
+}
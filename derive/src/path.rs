@@ -0,0 +1,144 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::LitStr;
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+/// A SPARQL property path written in a `path` option, such as `"ex:a/ex:b"`,
+/// `"^ex:knows"` or `"ex:a|ex:b"`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Path {
+  Iri(String),
+  Inverse(Box<Path>),
+  Sequence(Box<Path>, Box<Path>),
+  Alternative(Box<Path>, Box<Path>),
+  ZeroOrMore(Box<Path>),
+  OneOrMore(Box<Path>),
+  ZeroOrOne(Box<Path>),
+}
+
+impl Path {
+  /// Parses `value` as a property path whose compact IRIs use `prefixes`.
+  ///
+  /// Returns `None` when `value` is not a path.
+  pub fn parse(value: &str, prefixes: &[(String, LitStr)]) -> Option<Self> {
+    let mut parser = Parser {
+      input: value,
+      prefixes,
+    };
+
+    let path = parser.alternative()?;
+    parser.skip_whitespace();
+
+    parser.input.is_empty().then_some(path)
+  }
+}
+
+struct Parser<'a> {
+  input: &'a str,
+  prefixes: &'a [(String, LitStr)],
+}
+
+impl Parser<'_> {
+  fn alternative(&mut self) -> Option<Path> {
+    let mut path = self.sequence()?;
+    while self.eat('|') {
+      path = Path::Alternative(Box::new(path), Box::new(self.sequence()?));
+    }
+    Some(path)
+  }
+
+  fn sequence(&mut self) -> Option<Path> {
+    let mut path = self.element()?;
+    while self.eat('/') {
+      path = Path::Sequence(Box::new(path), Box::new(self.element()?));
+    }
+    Some(path)
+  }
+
+  fn element(&mut self) -> Option<Path> {
+    if self.eat('^') {
+      return Some(Path::Inverse(Box::new(self.element()?)));
+    }
+
+    let path = if self.eat('(') {
+      let path = self.alternative()?;
+      self.eat(')').then_some(path)?
+    } else {
+      self.iri()?
+    };
+
+    Some(if self.eat('*') {
+      Path::ZeroOrMore(Box::new(path))
+    } else if self.eat('+') {
+      Path::OneOrMore(Box::new(path))
+    } else if self.eat('?') {
+      Path::ZeroOrOne(Box::new(path))
+    } else {
+      path
+    })
+  }
+
+  fn iri(&mut self) -> Option<Path> {
+    self.skip_whitespace();
+
+    if let Some(rest) = self.input.strip_prefix('<') {
+      let (iri, rest) = rest.split_once('>')?;
+      self.input = rest;
+      return Some(Path::Iri(iri.to_owned()));
+    }
+
+    let end = self
+      .input
+      .find(|c: char| c.is_whitespace() || "|/^()*+?<>".contains(c))
+      .unwrap_or(self.input.len());
+    let (name, rest) = self.input.split_at(end);
+    self.input = rest;
+
+    if name == "a" {
+      return Some(Path::Iri(RDF_TYPE.to_owned()));
+    }
+
+    let (prefix, suffix) = name.split_once(':')?;
+    let (_, namespace) = self.prefixes.iter().find(|(name, _)| name == prefix)?;
+    Some(Path::Iri(namespace.value() + suffix))
+  }
+
+  fn eat(&mut self, c: char) -> bool {
+    self.skip_whitespace();
+    match self.input.strip_prefix(c) {
+      Some(rest) => {
+        self.input = rest;
+        true
+      }
+      None => false,
+    }
+  }
+
+  fn skip_whitespace(&mut self) {
+    self.input = self.input.trim_start();
+  }
+}
+
+impl ToTokens for Path {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let path =
+      quote::quote! { ::linked_data_sparql::reexport::spargebra::algebra::PropertyPathExpression };
+
+    tokens.extend(match self {
+      Self::Iri(iri) => quote::quote! {
+        #path::NamedNode(::linked_data_sparql::reexport::spargebra::term::NamedNode::new_unchecked(#iri))
+      },
+      Self::Inverse(inner) => quote::quote! { #path::Reverse(::std::boxed::Box::new(#inner)) },
+      Self::Sequence(first, second) => {
+        quote::quote! { #path::Sequence(::std::boxed::Box::new(#first), ::std::boxed::Box::new(#second)) }
+      }
+      Self::Alternative(first, second) => {
+        quote::quote! { #path::Alternative(::std::boxed::Box::new(#first), ::std::boxed::Box::new(#second)) }
+      }
+      Self::ZeroOrMore(inner) => quote::quote! { #path::ZeroOrMore(::std::boxed::Box::new(#inner)) },
+      Self::OneOrMore(inner) => quote::quote! { #path::OneOrMore(::std::boxed::Box::new(#inner)) },
+      Self::ZeroOrOne(inner) => quote::quote! { #path::ZeroOrOne(::std::boxed::Box::new(#inner)) },
+    });
+  }
+}
@@ -0,0 +1,39 @@
+use crate::{Error, IntoRdfTypes};
+use futures_util::{Stream, TryStreamExt};
+use rdf_types::dataset::IndexedBTreeDataset;
+use sparesults::QuerySolution;
+use spargebra::term::Triple;
+use spargebra::{Query, Update};
+use std::pin::Pin;
+
+pub type TripleStream = Pin<Box<dyn Stream<Item = Result<Triple, Error>> + Send>>;
+
+pub type SolutionStream = Pin<Box<dyn Stream<Item = Result<QuerySolution, Error>> + Send>>;
+
+/// Asynchronous counterpart of [`Executor`](crate::Executor) whose results
+/// are consumed as they arrive instead of being buffered first.
+pub trait AsyncExecutor {
+  fn construct(&self, query: Query) -> impl Future<Output = Result<TripleStream, Error>> + Send;
+
+  fn select(&self, query: Query) -> impl Future<Output = Result<SolutionStream, Error>> + Send;
+
+  fn ask(&self, query: Query) -> impl Future<Output = Result<bool, Error>> + Send;
+
+  fn update(&self, update: Update) -> impl Future<Output = Result<(), Error>> + Send;
+}
+
+/// Drains a CONSTRUCT result stream into the dataset type expected by
+/// `linked_data_next` deserialization.
+pub async fn collect_dataset(
+  triples: impl Stream<Item = Result<Triple, Error>>,
+) -> Result<IndexedBTreeDataset, Error> {
+  triples
+    .try_fold(
+      IndexedBTreeDataset::new(),
+      |mut dataset, triple| async move {
+        dataset.insert(triple.into_rdf_types()?);
+        Ok(dataset)
+      },
+    )
+    .await
+}
@@ -0,0 +1,157 @@
+use crate::async_executor::{SolutionStream, TripleStream};
+use crate::http::{N_TRIPLES, SPARQL_QUERY, SPARQL_RESULTS_JSON, SPARQL_UPDATE, TURTLE};
+use crate::{AsyncExecutor, Error};
+use futures_util::{TryStreamExt, stream};
+use oxttl::{NTriplesParser, TurtleParser};
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::{Client, Response};
+use sparesults::{
+  QueryResultsFormat, QueryResultsParser, TokioAsyncReaderQueryResultsParserOutput,
+};
+use spargebra::{Query, Update};
+use tokio::io::AsyncRead;
+use tokio_util::io::StreamReader;
+
+/// An [`AsyncExecutor`] speaking the SPARQL 1.1 Protocol to a remote
+/// endpoint, parsing response bodies while they are received.
+pub struct AsyncHttpExecutor {
+  client: Client,
+  query_endpoint: String,
+  update_endpoint: String,
+}
+
+impl AsyncHttpExecutor {
+  pub fn new(query_endpoint: impl Into<String>, update_endpoint: impl Into<String>) -> Self {
+    Self {
+      client: Client::new(),
+      query_endpoint: query_endpoint.into(),
+      update_endpoint: update_endpoint.into(),
+    }
+  }
+
+  pub fn with_client(self, client: Client) -> Self {
+    Self { client, ..self }
+  }
+
+  async fn post(
+    &self,
+    endpoint: &str,
+    content_type: &str,
+    accept: Option<&str>,
+    body: String,
+  ) -> Result<Response, Error> {
+    let mut request = self
+      .client
+      .post(endpoint)
+      .header(CONTENT_TYPE, content_type)
+      .body(body);
+
+    if let Some(accept) = accept {
+      request = request.header(ACCEPT, accept);
+    }
+
+    request
+      .send()
+      .await
+      .and_then(Response::error_for_status)
+      .map_err(Error::transport)
+  }
+
+  async fn query_results(
+    &self,
+    query: Query,
+  ) -> Result<TokioAsyncReaderQueryResultsParserOutput<impl AsyncRead + Send + Unpin + use<>>, Error>
+  {
+    let response = self
+      .post(
+        &self.query_endpoint,
+        SPARQL_QUERY,
+        Some(SPARQL_RESULTS_JSON),
+        query.to_string(),
+      )
+      .await?;
+
+    QueryResultsParser::from_format(QueryResultsFormat::Json)
+      .for_tokio_async_reader(body(response))
+      .await
+      .map_err(Error::parse)
+  }
+}
+
+impl AsyncExecutor for AsyncHttpExecutor {
+  async fn construct(&self, query: Query) -> Result<TripleStream, Error> {
+    let response = self
+      .post(
+        &self.query_endpoint,
+        SPARQL_QUERY,
+        Some(&format!("{N_TRIPLES}, {TURTLE};q=0.9")),
+        query.to_string(),
+      )
+      .await?;
+
+    let is_turtle = response
+      .headers()
+      .get(CONTENT_TYPE)
+      .and_then(|value| value.to_str().ok())
+      .is_some_and(|value| value.starts_with(TURTLE));
+
+    let triples: TripleStream = if is_turtle {
+      let parser = TurtleParser::new().for_tokio_async_reader(body(response));
+
+      Box::pin(stream::unfold(parser, |mut parser| async move {
+        let triple = parser.next().await?;
+        Some((triple.map_err(Error::parse), parser))
+      }))
+    } else {
+      let parser = NTriplesParser::new().for_tokio_async_reader(body(response));
+
+      Box::pin(stream::unfold(parser, |mut parser| async move {
+        let triple = parser.next().await?;
+        Some((triple.map_err(Error::parse), parser))
+      }))
+    };
+
+    Ok(triples)
+  }
+
+  async fn select(&self, query: Query) -> Result<SolutionStream, Error> {
+    match self.query_results(query).await? {
+      TokioAsyncReaderQueryResultsParserOutput::Solutions(solutions) => {
+        let solutions: SolutionStream =
+          Box::pin(stream::unfold(solutions, |mut solutions| async move {
+            let solution = solutions.next().await?;
+            Some((solution.map_err(Error::parse), solutions))
+          }));
+
+        Ok(solutions)
+      }
+      TokioAsyncReaderQueryResultsParserOutput::Boolean(_) => Err(Error::UnexpectedResults),
+    }
+  }
+
+  async fn ask(&self, query: Query) -> Result<bool, Error> {
+    match self.query_results(query).await? {
+      TokioAsyncReaderQueryResultsParserOutput::Boolean(value) => Ok(value),
+      TokioAsyncReaderQueryResultsParserOutput::Solutions(_) => Err(Error::UnexpectedResults),
+    }
+  }
+
+  async fn update(&self, update: Update) -> Result<(), Error> {
+    self
+      .post(
+        &self.update_endpoint,
+        SPARQL_UPDATE,
+        None,
+        update.to_string(),
+      )
+      .await?;
+
+    Ok(())
+  }
+}
+
+fn body(response: Response) -> impl AsyncRead + Send + Unpin {
+  StreamReader::new(Box::pin(
+    response.bytes_stream().map_err(std::io::Error::other),
+  ))
+}
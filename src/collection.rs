@@ -0,0 +1,367 @@
+use crate::{ConstructQuery, Join, ToConstructQuery};
+use iref::{Iri, IriBuf};
+use linked_data_next::{
+  Context, FromLinkedDataError, LinkedDataDeserializePredicateObjects,
+  LinkedDataDeserializeSubject, LinkedDataPredicateObjects, LinkedDataResource, LinkedDataSubject,
+  PredicateObjectsVisitor, ResourceInterpretation, SubjectVisitor,
+};
+use rdf_types::dataset::PatternMatchingDataset;
+use rdf_types::interpretation::{IriInterpretation, ReverseIriInterpretation};
+use rdf_types::pattern::CanonicalQuadPattern;
+use rdf_types::vocabulary::{IriVocabulary, IriVocabularyMut};
+use rdf_types::{Interpretation, Quad, Vocabulary};
+use spargebra::term::Variable;
+use std::ops::Deref;
+
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+/// The namespace prefix of the `rdf:_1`, `rdf:_2`, ... container membership
+/// properties.
+const RDF_MEMBERSHIP: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#_";
+
+fn iri(value: &'static str) -> &'static Iri {
+  Iri::new(value).expect("valid IRI")
+}
+
+/// The items of an RDF collection, in order.
+///
+/// Its pattern matches the whole `rdf:first`/`rdf:rest` chain, which is
+/// written back in the CONSTRUCT template down to `rdf:nil`, so the items are
+/// deserialized in the order of the collection.
+///
+/// This wrapper stands in for a `#[ld(list)]` option on `Vec<T>` fields: the
+/// `Serialize` and `Deserialize` derives of `linked_data_next` reject unknown
+/// `#[ld]` options, and deserialize a `Vec<T>` as an unordered multi-valued
+/// property, so the collection has to be carried by the field type.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct List<T>(Vec<T>);
+
+/// The members of an RDF container, such as an `rdf:Seq`, ordered by their
+/// `rdf:_1`, `rdf:_2`, ... membership properties.
+///
+/// Like [`List`], this wrapper stands in for a `#[ld(seq)]` option on `Vec<T>`
+/// fields, which the `linked_data_next` derives would not accept.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Seq<T>(Vec<T>);
+
+macro_rules! collection_common {
+  ($($collection:ident),*) => {
+    $(
+      impl<T> $collection<T> {
+        pub fn new(items: Vec<T>) -> Self {
+          Self(items)
+        }
+
+        pub fn into_vec(self) -> Vec<T> {
+          self.0
+        }
+      }
+
+      impl<T> Deref for $collection<T> {
+        type Target = [T];
+
+        fn deref(&self) -> &[T] {
+          &self.0
+        }
+      }
+
+      impl<T> From<Vec<T>> for $collection<T> {
+        fn from(items: Vec<T>) -> Self {
+          Self(items)
+        }
+      }
+
+      impl<T> FromIterator<T> for $collection<T> {
+        fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self {
+          Self(items.into_iter().collect())
+        }
+      }
+    )*
+  };
+}
+
+collection_common!(List, Seq);
+
+impl<T> ToConstructQuery for List<T>
+where
+  T: ToConstructQuery,
+{
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    ConstructQuery::default()
+      .join(ConstructQuery::new_list(binding_variable, T::to_query_with_binding).optional())
+      .optional()
+  }
+}
+
+impl<T> ToConstructQuery for Seq<T>
+where
+  T: ToConstructQuery,
+{
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    ConstructQuery::default()
+      .join(ConstructQuery::new_container(binding_variable, T::to_query_with_binding).optional())
+      .optional()
+  }
+}
+
+/// The blank cell of a collection holding `items`, which is not empty.
+struct Cell<'a, T>(&'a [T]);
+
+impl<I: Interpretation, V: Vocabulary, T> LinkedDataResource<I, V> for Cell<'_, T> {
+  fn interpretation(
+    &self,
+    _vocabulary: &mut V,
+    _interpretation: &mut I,
+  ) -> ResourceInterpretation<'_, I, V> {
+    ResourceInterpretation::Uninterpreted(None)
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary + IriVocabularyMut, T> LinkedDataSubject<I, V> for Cell<'_, T>
+where
+  T: LinkedDataSubject<I, V> + LinkedDataResource<I, V>,
+{
+  fn visit_subject<S>(&self, mut serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: SubjectVisitor<I, V>,
+  {
+    serializer.predicate(iri(RDF_FIRST), &self.0[..1])?;
+    match &self.0[1..] {
+      [] => serializer.predicate(iri(RDF_REST), iri(RDF_NIL))?,
+      rest => serializer.predicate(iri(RDF_REST), &Cell(rest))?,
+    }
+    serializer.end()
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary + IriVocabularyMut, T> LinkedDataPredicateObjects<I, V>
+  for Cell<'_, T>
+where
+  T: LinkedDataSubject<I, V> + LinkedDataResource<I, V>,
+{
+  fn visit_objects<S>(&self, mut visitor: S) -> Result<S::Ok, S::Error>
+  where
+    S: PredicateObjectsVisitor<I, V>,
+  {
+    visitor.object(self)?;
+    visitor.end()
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary + IriVocabularyMut, T> LinkedDataPredicateObjects<I, V>
+  for List<T>
+where
+  T: LinkedDataSubject<I, V> + LinkedDataResource<I, V>,
+{
+  fn visit_objects<S>(&self, visitor: S) -> Result<S::Ok, S::Error>
+  where
+    S: PredicateObjectsVisitor<I, V>,
+  {
+    if self.0.is_empty() {
+      iri(RDF_NIL).visit_objects(visitor)
+    } else {
+      Cell(&self.0).visit_objects(visitor)
+    }
+  }
+}
+
+/// The blank container holding `members`.
+struct Container<'a, T>(&'a [T]);
+
+impl<I: Interpretation, V: Vocabulary, T> LinkedDataResource<I, V> for Container<'_, T> {
+  fn interpretation(
+    &self,
+    _vocabulary: &mut V,
+    _interpretation: &mut I,
+  ) -> ResourceInterpretation<'_, I, V> {
+    ResourceInterpretation::Uninterpreted(None)
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary + IriVocabularyMut, T> LinkedDataSubject<I, V>
+  for Container<'_, T>
+where
+  T: LinkedDataSubject<I, V> + LinkedDataResource<I, V>,
+{
+  fn visit_subject<S>(&self, mut serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: SubjectVisitor<I, V>,
+  {
+    for (index, member) in self.0.iter().enumerate() {
+      let membership = IriBuf::new(format!("{RDF_MEMBERSHIP}{}", index + 1)).expect("valid IRI");
+      serializer.predicate(&membership, std::slice::from_ref(member))?;
+    }
+    serializer.end()
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary + IriVocabularyMut, T> LinkedDataPredicateObjects<I, V>
+  for Seq<T>
+where
+  T: LinkedDataSubject<I, V> + LinkedDataResource<I, V>,
+{
+  fn visit_objects<S>(&self, mut visitor: S) -> Result<S::Ok, S::Error>
+  where
+    S: PredicateObjectsVisitor<I, V>,
+  {
+    visitor.object(&Container(&self.0))?;
+    visitor.end()
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary, T> LinkedDataDeserializePredicateObjects<I, V> for List<T>
+where
+  V: IriVocabulary,
+  I: IriInterpretation<V::Iri> + ReverseIriInterpretation<Iri = V::Iri>,
+  I::Resource: Clone + PartialEq,
+  T: LinkedDataDeserializeSubject<I, V>,
+{
+  fn deserialize_objects_in<'a, D>(
+    vocabulary: &V,
+    interpretation: &I,
+    dataset: &D,
+    graph: Option<&I::Resource>,
+    objects: impl IntoIterator<Item = &'a I::Resource>,
+    context: Context<I>,
+  ) -> Result<Self, FromLinkedDataError>
+  where
+    I::Resource: 'a,
+    D: PatternMatchingDataset<Resource = I::Resource>,
+  {
+    let Some(head) = single(vocabulary, interpretation, objects, context)? else {
+      return Ok(Self(Vec::new()));
+    };
+
+    let resource = |value| {
+      vocabulary
+        .get(iri(value))
+        .and_then(|iri| interpretation.iri_interpretation(&iri))
+    };
+    let (first, rest, nil) = (resource(RDF_FIRST), resource(RDF_REST), resource(RDF_NIL));
+
+    let mut items = Vec::new();
+    let mut visited = Vec::new();
+    let mut cell = head.clone();
+    while nil.as_ref() != Some(&cell) {
+      if visited.contains(&cell) {
+        return Err(FromLinkedDataError::InvalidSubject {
+          context: context.into_iris(vocabulary, interpretation),
+          subject: None,
+        });
+      }
+
+      let (Some(first), Some(rest)) = (&first, &rest) else {
+        return Err(FromLinkedDataError::MissingRequiredValue(
+          context.into_iris(vocabulary, interpretation),
+        ));
+      };
+
+      let item = single(
+        vocabulary,
+        interpretation,
+        dataset.quad_objects(graph, &cell, first),
+        context,
+      )?
+      .ok_or_else(|| {
+        FromLinkedDataError::MissingRequiredValue(context.into_iris(vocabulary, interpretation))
+      })?;
+      items.push(T::deserialize_subject_in(
+        vocabulary,
+        interpretation,
+        dataset,
+        graph,
+        item,
+        context,
+      )?);
+
+      let next = single(
+        vocabulary,
+        interpretation,
+        dataset.quad_objects(graph, &cell, rest),
+        context,
+      )?
+      .ok_or_else(|| {
+        FromLinkedDataError::MissingRequiredValue(context.into_iris(vocabulary, interpretation))
+      })?
+      .clone();
+      visited.push(std::mem::replace(&mut cell, next));
+    }
+
+    Ok(Self(items))
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary, T> LinkedDataDeserializePredicateObjects<I, V> for Seq<T>
+where
+  V: IriVocabulary,
+  I: ReverseIriInterpretation<Iri = V::Iri>,
+  T: LinkedDataDeserializeSubject<I, V>,
+{
+  fn deserialize_objects_in<'a, D>(
+    vocabulary: &V,
+    interpretation: &I,
+    dataset: &D,
+    graph: Option<&I::Resource>,
+    objects: impl IntoIterator<Item = &'a I::Resource>,
+    context: Context<I>,
+  ) -> Result<Self, FromLinkedDataError>
+  where
+    I::Resource: 'a,
+    D: PatternMatchingDataset<Resource = I::Resource>,
+  {
+    let Some(container) = single(vocabulary, interpretation, objects, context)? else {
+      return Ok(Self(Vec::new()));
+    };
+
+    let mut members = dataset
+      .quad_pattern_matching(CanonicalQuadPattern::from_option_quad(Quad(
+        Some(container),
+        None,
+        None,
+        Some(graph),
+      )))
+      .filter_map(|Quad(_, predicate, member, _)| {
+        let index = interpretation.iris_of(predicate).find_map(|iri| {
+          vocabulary
+            .iri(iri)?
+            .as_str()
+            .strip_prefix(RDF_MEMBERSHIP)?
+            .parse::<usize>()
+            .ok()
+        })?;
+        Some((index, member))
+      })
+      .collect::<Vec<_>>();
+    members.sort_by_key(|(index, _)| *index);
+
+    members
+      .into_iter()
+      .map(|(_, member)| {
+        T::deserialize_subject_in(vocabulary, interpretation, dataset, graph, member, context)
+      })
+      .collect::<Result<_, _>>()
+      .map(Self)
+  }
+}
+
+/// The only resource of `objects`, if any.
+fn single<'a, I, V>(
+  vocabulary: &V,
+  interpretation: &I,
+  objects: impl IntoIterator<Item = &'a I::Resource>,
+  context: Context<I>,
+) -> Result<Option<&'a I::Resource>, FromLinkedDataError>
+where
+  I: ReverseIriInterpretation<Iri = V::Iri>,
+  I::Resource: 'a,
+  V: IriVocabulary,
+{
+  let mut objects = objects.into_iter();
+  match (objects.next(), objects.next()) {
+    (object, None) => Ok(object),
+    _ => Err(FromLinkedDataError::TooManyValues(
+      context.into_iris(vocabulary, interpretation),
+    )),
+  }
+}
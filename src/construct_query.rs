@@ -1,16 +1,40 @@
 use crate::and::And;
+use crate::datatypes::TemplateDatatypes;
 use crate::join::Join;
+use crate::roots::ROOT_PREDICATE;
 use crate::to_construct_query::ToConstructQuery;
 use crate::union::Union;
+use crate::xsd;
 use spargebra::Query;
-use spargebra::algebra::{Expression, GraphPattern};
-use spargebra::term::{NamedNode, NamedNodePattern, TermPattern, TriplePattern, Variable};
+use spargebra::algebra::{
+  AggregateExpression, AggregateFunction, Expression, Function, GraphPattern,
+  PropertyPathExpression,
+};
+use spargebra::term::{Literal, NamedNode, NamedNodePattern, TermPattern, TriplePattern, Variable};
 use sparopt::Optimizer;
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+/// The namespace prefix of the `rdf:_1`, `rdf:_2`, ... container membership
+/// properties.
+const RDF_MEMBERSHIP: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#_";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDFS_SUB_CLASS_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subClassOf";
 
 #[derive(Default)]
 pub struct ConstructQuery {
-  construct_template: Vec<TriplePattern>,
-  where_pattern: GraphPattern,
+  pub(crate) construct_template: Vec<TriplePattern>,
+  pub(crate) where_pattern: GraphPattern,
+  datatypes: Vec<(Variable, NamedNode)>,
+  guards: Vec<(Variable, Expression)>,
+  tagged: Vec<Variable>,
+  language_hops: Vec<(Variable, NamedNode, Variable)>,
+  constraints: Vec<(Variable, Expression)>,
+  optional: bool,
 }
 
 impl ConstructQuery {
@@ -28,6 +52,7 @@ impl ConstructQuery {
     Self {
       construct_template: patterns.clone(),
       where_pattern: GraphPattern::Bgp { patterns },
+      ..Default::default()
     }
   }
 
@@ -41,7 +66,156 @@ impl ConstructQuery {
   {
     let object = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
 
-    Self::new(subject, predicate, object.clone()).join(to_query_with_binding(object))
+    Self::new_with_object(subject, predicate, object, to_query_with_binding)
+  }
+
+  /// Binds the object of `path` from `subject` to the pattern of
+  /// `to_query_with_binding`.
+  ///
+  /// The template holds the matches as `predicate` triples, so they are
+  /// deserialized as the values of that predicate.
+  pub fn new_with_path<F>(
+    subject: Variable,
+    predicate: NamedNode,
+    path: PropertyPathExpression,
+    to_query_with_binding: F,
+  ) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    let object = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
+
+    let mut child = to_query_with_binding(object.clone());
+    let optional = std::mem::take(&mut child.optional);
+
+    let mut query = Self {
+      construct_template: vec![TriplePattern {
+        subject: subject.clone().into(),
+        predicate: predicate.into(),
+        object: object.clone().into(),
+      }],
+      where_pattern: GraphPattern::Path {
+        subject: subject.into(),
+        path,
+        object: object.into(),
+      },
+      ..Default::default()
+    }
+    .join(child);
+    query.apply_constraints();
+    query.optional = optional;
+    query
+  }
+
+  /// Binds the object reached from `subject` through the sequence of
+  /// `predicates` to the pattern of `to_query_with_binding`, matched with a
+  /// single sequence path.
+  ///
+  /// The template links them through a fresh blank node per intermediate
+  /// step.
+  pub fn new_with_chain<F>(
+    subject: Variable,
+    predicates: Vec<NamedNode>,
+    to_query_with_binding: F,
+  ) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    let object = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
+
+    let path = predicates
+      .iter()
+      .cloned()
+      .map(PropertyPathExpression::NamedNode)
+      .reduce(|left, right| PropertyPathExpression::Sequence(Box::new(left), Box::new(right)));
+    let Some(path) = path else {
+      return to_query_with_binding(subject);
+    };
+
+    let mut child = to_query_with_binding(object.clone());
+    let optional = std::mem::take(&mut child.optional);
+
+    let mut construct_template = Vec::new();
+    let mut node = TermPattern::from(subject.clone());
+    let mut predicates = predicates.into_iter().peekable();
+    while let Some(predicate) = predicates.next() {
+      let next = match predicates.peek() {
+        Some(_) => TermPattern::from(spargebra::term::BlankNode::default()),
+        None => object.clone().into(),
+      };
+      construct_template.push(TriplePattern {
+        subject: node,
+        predicate: predicate.into(),
+        object: next.clone(),
+      });
+      node = next;
+    }
+
+    let mut query = Self {
+      construct_template,
+      where_pattern: GraphPattern::Path {
+        subject: subject.into(),
+        path,
+        object: object.into(),
+      },
+      ..Default::default()
+    }
+    .join(child);
+    query.apply_constraints();
+    query.optional = optional;
+    query
+  }
+
+  pub fn join_with_path<F>(
+    self,
+    subject: Variable,
+    predicate: NamedNode,
+    path: PropertyPathExpression,
+    to_query_with_binding: F,
+  ) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    self.join(Self::new_with_path(
+      subject,
+      predicate,
+      path,
+      to_query_with_binding,
+    ))
+  }
+
+  pub fn union_with_path<F>(
+    self,
+    subject: Variable,
+    predicate: NamedNode,
+    path: PropertyPathExpression,
+    to_query_with_binding: F,
+  ) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    self.union(Self::new_with_path(
+      subject,
+      predicate,
+      path,
+      to_query_with_binding,
+    ))
+  }
+
+  pub fn union_with_chain<F>(
+    self,
+    subject: Variable,
+    predicates: Vec<NamedNode>,
+    to_query_with_binding: F,
+  ) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    self.union(Self::new_with_chain(
+      subject,
+      predicates,
+      to_query_with_binding,
+    ))
   }
 
   pub fn union_with_binding<F>(
@@ -55,9 +229,15 @@ impl ConstructQuery {
   {
     let object = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
 
-    self
-      .union(Self::new(subject, predicate, object.clone()))
-      .join(to_query_with_binding(object))
+    let mut query = self
+      .union(Self::new(
+        subject.clone(),
+        predicate.clone(),
+        object.clone(),
+      ))
+      .join(to_query_with_binding(object.clone()));
+    query.record_hop(subject, predicate, object);
+    query
   }
 
   pub fn join_with_binding<F>(
@@ -69,38 +249,844 @@ impl ConstructQuery {
   where
     F: FnOnce(Variable) -> Self,
   {
-    let object = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
+    self.join(Self::new_with_binding(
+      subject,
+      predicate,
+      to_query_with_binding,
+    ))
+  }
 
-    self
-      .join(Self::new(subject, predicate, object.clone()))
-      .join(to_query_with_binding(object))
+  /// Same as [`Self::new_with_binding`], binding the object to `object`
+  /// rather than to a fresh variable, so other patterns can refer to it.
+  pub fn new_with_object<F>(
+    subject: Variable,
+    predicate: NamedNode,
+    object: Variable,
+    to_query_with_binding: F,
+  ) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    let mut child = to_query_with_binding(object.clone());
+    let optional = std::mem::take(&mut child.optional);
+
+    let mut query = Self::new(subject.clone(), predicate.clone(), object.clone()).join(child);
+    query.apply_constraints();
+    query.record_hop(subject, predicate, object);
+    query.optional = optional;
+    query
+  }
+
+  pub fn join_with_object<F>(
+    self,
+    subject: Variable,
+    predicate: NamedNode,
+    object: Variable,
+    to_query_with_binding: F,
+  ) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    self.join(Self::new_with_object(
+      subject,
+      predicate,
+      object,
+      to_query_with_binding,
+    ))
+  }
+
+  /// Binds every resource reachable from `subject` the way the resources
+  /// bound to `nested` are, through zero or more repetitions of the patterns
+  /// linking them to `subject`, to the pattern of `to_query_with_binding`, so
+  /// chains of any length come back in one query.
+  ///
+  /// The nested resources whose link cannot be read from the where pattern
+  /// are left unexpanded.
+  pub(crate) fn recursive<F>(
+    self,
+    subject: Variable,
+    nested: &[Variable],
+    to_query_with_binding: F,
+  ) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    let edges = edges(&self.where_pattern);
+
+    let mut paths = Vec::new();
+    for path in nested
+      .iter()
+      .filter_map(|nested| link(&edges, &subject, nested))
+    {
+      if !paths.contains(&path) {
+        paths.push(path);
+      }
+    }
+
+    let Some(path) = paths
+      .into_iter()
+      .reduce(|left, right| PropertyPathExpression::Alternative(Box::new(left), Box::new(right)))
+    else {
+      return self;
+    };
+
+    let node = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
+
+    self.join(
+      Self {
+        where_pattern: GraphPattern::Path {
+          subject: subject.into(),
+          path: PropertyPathExpression::ZeroOrMore(Box::new(path)),
+          object: node.clone().into(),
+        },
+        ..Default::default()
+      }
+      .join(to_query_with_binding(node)),
+    )
+  }
+
+  /// Binds every item of the RDF collection starting at `list` to the
+  /// pattern of `to_query_with_binding`.
+  ///
+  /// The template holds the `rdf:first` and `rdf:rest` triples of every cell,
+  /// down to `rdf:nil`, so the order of the items is kept.
+  pub fn new_list<F>(list: Variable, to_query_with_binding: F) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    let [cell, item, next] = std::array::from_fn(|_| {
+      Variable::new_unchecked(spargebra::term::BlankNode::default().into_string())
+    });
+    let rest = NamedNode::new_unchecked(RDF_REST);
+
+    let patterns = vec![
+      TriplePattern {
+        subject: cell.clone().into(),
+        predicate: NamedNode::new_unchecked(RDF_FIRST).into(),
+        object: item.clone().into(),
+      },
+      TriplePattern {
+        subject: cell.clone().into(),
+        predicate: rest.clone().into(),
+        object: next.into(),
+      },
+    ];
+
+    let mut cells = Self {
+      construct_template: patterns.clone(),
+      where_pattern: GraphPattern::Path {
+        subject: list.into(),
+        path: PropertyPathExpression::ZeroOrMore(Box::new(PropertyPathExpression::NamedNode(rest))),
+        object: cell.into(),
+      }
+      .join(GraphPattern::Bgp { patterns }),
+      ..Default::default()
+    }
+    .join(to_query_with_binding(item));
+    cells.apply_constraints();
+    cells
+  }
+
+  /// Binds every member of the RDF container `container`, such as an
+  /// `rdf:Seq`, to the pattern of `to_query_with_binding`.
+  ///
+  /// The template holds the `rdf:_1`, `rdf:_2`, ... membership triples, from
+  /// which the order of the members can be restored.
+  pub fn new_container<F>(container: Variable, to_query_with_binding: F) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    let [membership, member] = std::array::from_fn(|_| {
+      Variable::new_unchecked(spargebra::term::BlankNode::default().into_string())
+    });
+
+    let patterns = vec![TriplePattern {
+      subject: container.into(),
+      predicate: membership.clone().into(),
+      object: member.clone().into(),
+    }];
+    let is_membership = Expression::FunctionCall(
+      Function::StrStarts,
+      vec![
+        Expression::FunctionCall(Function::Str, vec![Expression::Variable(membership)]),
+        Expression::Literal(Literal::new_simple_literal(RDF_MEMBERSHIP)),
+      ],
+    );
+
+    let mut members = Self {
+      construct_template: patterns.clone(),
+      where_pattern: GraphPattern::Filter {
+        expr: is_membership,
+        inner: Box::new(GraphPattern::Bgp { patterns }),
+      },
+      ..Default::default()
+    }
+    .join(to_query_with_binding(member));
+    members.apply_constraints();
+    members
   }
 
   pub fn join_with(self, subject: Variable, predicate: NamedNode, object: NamedNode) -> Self {
     self.join(Self::new(subject, predicate, object))
   }
 
+  /// Binds the value of `expression`, over the variables of the pattern built
+  /// so far, to the pattern of `to_query_with_binding`, and links it from
+  /// `subject` by `predicate` in the template.
+  pub fn join_with_expression<F>(
+    mut self,
+    subject: Variable,
+    predicate: NamedNode,
+    expression: Expression,
+    to_query_with_binding: F,
+  ) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    let value = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
+
+    self.where_pattern = GraphPattern::Extend {
+      inner: Box::new(self.where_pattern),
+      variable: value.clone(),
+      expression,
+    };
+    self.construct_template.push(TriplePattern {
+      subject: subject.into(),
+      predicate: predicate.into(),
+      object: value.clone().into(),
+    });
+
+    self.join(to_query_with_binding(value))
+  }
+
+  /// Binds the `function` aggregate of the objects of `path` from `subject`,
+  /// taking each object once with `distinct`, to the pattern of
+  /// `to_query_with_binding`, and links it from `subject` by `predicate` in
+  /// the template.
+  ///
+  /// The aggregate is computed by a sub-select grouping the objects of the
+  /// subjects matched by the pattern so far, not of every subject of the
+  /// store. Counts and sums are zero for the resources without such objects;
+  /// the other aggregates are left unbound.
+  pub fn join_with_aggregate<F>(
+    mut self,
+    subject: Variable,
+    predicate: NamedNode,
+    path: PropertyPathExpression,
+    function: AggregateFunction,
+    distinct: bool,
+    to_query_with_binding: F,
+  ) -> Self
+  where
+    F: FnOnce(Variable) -> Self,
+  {
+    let [object, aggregate, value] = std::array::from_fn(|_| {
+      Variable::new_unchecked(spargebra::term::BlankNode::default().into_string())
+    });
+
+    let zero = matches!(function, AggregateFunction::Count | AggregateFunction::Sum);
+    let subjects = GraphPattern::Distinct {
+      inner: Box::new(GraphPattern::Project {
+        inner: Box::new(self.where_pattern.clone()),
+        variables: vec![subject.clone()],
+      }),
+    };
+    let group = GraphPattern::Project {
+      inner: Box::new(GraphPattern::Group {
+        inner: Box::new(subjects.join(GraphPattern::Path {
+          subject: subject.clone().into(),
+          path,
+          object: object.clone().into(),
+        })),
+        variables: vec![subject.clone()],
+        aggregates: vec![(
+          aggregate.clone(),
+          AggregateExpression::FunctionCall {
+            distinct,
+            name: function,
+            expr: Expression::Variable(object),
+          },
+        )],
+      }),
+      variables: vec![subject.clone(), aggregate.clone()],
+    };
+
+    let mut expression = if zero {
+      Expression::Coalesce(vec![
+        Expression::Variable(aggregate),
+        Expression::Literal(Literal::from(0)),
+      ])
+    } else {
+      Expression::Variable(aggregate)
+    };
+
+    // `COUNT` yields an `xsd:integer` and `SUM` the type of its operands, so
+    // the value is cast to the datatype expected by the field.
+    let value_query = to_query_with_binding(value.clone());
+    let datatype = value_query
+      .datatypes
+      .iter()
+      .find(|(variable, _)| *variable == value)
+      .map(|(_, datatype)| datatype.clone());
+    if let Some(datatype) = datatype {
+      expression = Expression::FunctionCall(
+        Function::StrDt,
+        vec![
+          Expression::FunctionCall(Function::Str, vec![expression]),
+          Expression::NamedNode(datatype),
+        ],
+      );
+    }
+
+    self.where_pattern = GraphPattern::Extend {
+      inner: Box::new(GraphPattern::LeftJoin {
+        left: Box::new(self.where_pattern),
+        right: Box::new(group),
+        expression: None,
+      }),
+      variable: value.clone(),
+      expression,
+    };
+    self.construct_template.push(TriplePattern {
+      subject: subject.into(),
+      predicate: predicate.into(),
+      object: value.into(),
+    });
+
+    self.join(value_query)
+  }
+
+  /// Requires `subject` to be an instance of `class` or of one of its
+  /// subclasses, through `rdf:type/rdfs:subClassOf*`.
+  ///
+  /// The template holds `subject rdf:type class`, whatever the actual type.
+  pub fn join_with_subclass_of(self, subject: Variable, class: NamedNode) -> Self {
+    let rdf_type = NamedNode::new_unchecked(RDF_TYPE);
+    let path = PropertyPathExpression::Sequence(
+      Box::new(PropertyPathExpression::NamedNode(rdf_type.clone())),
+      Box::new(PropertyPathExpression::ZeroOrMore(Box::new(
+        PropertyPathExpression::NamedNode(NamedNode::new_unchecked(RDFS_SUB_CLASS_OF)),
+      ))),
+    );
+
+    self.join(Self {
+      construct_template: vec![TriplePattern {
+        subject: subject.clone().into(),
+        predicate: rdf_type.into(),
+        object: class.clone().into(),
+      }],
+      where_pattern: GraphPattern::Path {
+        subject: subject.into(),
+        path,
+        object: class.into(),
+      },
+      ..Default::default()
+    })
+  }
+
+  /// Marks the pattern as optional: it is left-joined to the patterns it is
+  /// joined to, as for `Option` and `Vec` fields.
+  pub fn optional(mut self) -> Self {
+    self.optional = true;
+    self
+  }
+
+  /// Adds a marker triple on `variable` to the template only, so the root
+  /// resources can be told apart in the result with
+  /// [`extract_roots`](crate::extract_roots).
+  pub fn mark_root(mut self, variable: Variable) -> Self {
+    self.construct_template.push(TriplePattern {
+      subject: variable.into(),
+      predicate: NamedNode::new_unchecked(ROOT_PREDICATE).into(),
+      object: Literal::from(true).into(),
+    });
+    self
+  }
+
   pub fn filter_variable(self, variable: Variable, id: NamedNode) -> Self {
     let expr = Expression::Equal(
       Box::new(Expression::Variable(variable)),
       Box::new(Expression::NamedNode(id)),
     );
     Self {
-      construct_template: self.construct_template,
       where_pattern: GraphPattern::Filter {
         expr,
         inner: Box::new(self.where_pattern),
       },
+      ..self
+    }
+  }
+
+  /// Records the XSD datatype the values bound to `variable` are expected to
+  /// have, along with the matching guard.
+  pub fn with_datatype(mut self, variable: Variable, datatype: NamedNode) -> Self {
+    let guard = datatype_guard(&variable, &datatype);
+
+    self.datatypes.push((variable.clone(), datatype));
+    self.with_guard(variable, guard)
+  }
+
+  /// Records a condition the values bound to `variable` must satisfy, applied
+  /// by [`ConstructQuery::guard_leaves`].
+  pub fn with_guard(mut self, variable: Variable, guard: Expression) -> Self {
+    self.guards.push((variable, guard));
+    self
+  }
+
+  /// Records that the values bound to `variable` may be language-tagged, so
+  /// they take part in [`ConstructQuery::prefer_languages`].
+  pub fn with_language_tags(mut self, variable: Variable) -> Self {
+    self.tagged.push(variable);
+    self
+  }
+
+  /// Keeps, for every property with language-tagged values, only the values
+  /// in the most preferred language available on each resource.
+  ///
+  /// `languages` are matched in order with `LANGMATCHES`, so `"*"` matches
+  /// any tag. Untagged values come after every listed language, and values in
+  /// any other language come last. `String` values are emitted without their
+  /// language tag.
+  pub fn prefer_languages(mut self, languages: &[&str]) -> Self {
+    for (subject, predicate, object) in std::mem::take(&mut self.language_hops) {
+      let other = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
+
+      let better = GraphPattern::Filter {
+        expr: precedes(&other, &object, languages),
+        inner: Box::new(GraphPattern::Bgp {
+          patterns: vec![TriplePattern {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            object: other.into(),
+          }],
+        }),
+      };
+
+      let best = Expression::Not(Box::new(Expression::Exists(Box::new(better))));
+      self.where_pattern = filter_bound(self.where_pattern, &[(object.clone(), best)]);
+
+      if self
+        .datatypes
+        .iter()
+        .any(|(variable, datatype)| variable == &object && datatype.as_str() == xsd::STRING)
+      {
+        self.strip_language_tags(object);
+      }
+    }
+
+    self
+  }
+
+  /// Emits `STR(?variable)` in the template instead of `variable`, and only
+  /// requires its values to be strings, tagged or not.
+  fn strip_language_tags(&mut self, variable: Variable) {
+    let plain = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
+
+    for pattern in &mut self.construct_template {
+      if pattern.object == TermPattern::Variable(variable.clone()) {
+        pattern.object = plain.clone().into();
+      }
+    }
+
+    let where_pattern = std::mem::replace(
+      &mut self.where_pattern,
+      GraphPattern::Bgp {
+        patterns: Vec::new(),
+      },
+    );
+    self.where_pattern = GraphPattern::Extend {
+      inner: Box::new(where_pattern),
+      variable: plain,
+      expression: Expression::FunctionCall(
+        Function::Str,
+        vec![Expression::Variable(variable.clone())],
+      ),
+    };
+
+    let guard = Expression::And(
+      Box::new(Expression::FunctionCall(
+        Function::IsLiteral,
+        vec![Expression::Variable(variable.clone())],
+      )),
+      Box::new(Expression::In(
+        Box::new(Expression::FunctionCall(
+          Function::Datatype,
+          vec![Expression::Variable(variable.clone())],
+        )),
+        vec![
+          Expression::NamedNode(NamedNode::new_unchecked(xsd::STRING)),
+          Expression::NamedNode(NamedNode::new_unchecked(RDF_LANG_STRING)),
+        ],
+      )),
+    );
+
+    self.guards.retain(|(guarded, _)| guarded != &variable);
+    self.guards.push((variable, guard));
+  }
+
+  /// Records a condition the values bound to `variable` must satisfy, applied
+  /// as soon as the triple binding them is joined.
+  pub fn with_constraint(mut self, variable: Variable, constraint: Expression) -> Self {
+    self.constraints.push((variable, constraint));
+    self
+  }
+
+  /// Requires the values bound to `variable` to be IRIs.
+  pub fn require_iri(self, variable: Variable) -> Self {
+    let constraint = Expression::FunctionCall(
+      Function::IsIri,
+      vec![Expression::Variable(variable.clone())],
+    );
+
+    self.with_constraint(variable, constraint)
+  }
+
+  pub(crate) fn apply_constraints(&mut self) {
+    let constraints = std::mem::take(&mut self.constraints);
+    self.where_pattern = filter_bound(std::mem::take(&mut self.where_pattern), &constraints);
+  }
+
+  /// Records the hop from `subject` to `object` when the values bound to
+  /// `object` may be language-tagged.
+  fn record_hop(&mut self, subject: Variable, predicate: NamedNode, object: Variable) {
+    if self.tagged.contains(&object) {
+      self.language_hops.push((subject, predicate, object));
     }
   }
+
+  /// Filters out the solutions where a leaf value does not satisfy its guard,
+  /// so a single malformed triple only drops that value instead of failing
+  /// the deserialization of the whole resource.
+  ///
+  /// The guards of optional fields are applied inside their optional pattern,
+  /// so a malformed value leaves the field unbound instead of dropping the
+  /// resource.
+  pub fn guard_leaves(mut self) -> Self {
+    let mut guards = std::mem::take(&mut self.guards);
+    let where_pattern = place_guards(self.where_pattern, &mut guards);
+    self.where_pattern = filter_bound(where_pattern, &guards);
+    self
+  }
+
+  /// Re-binds every variable with a recorded datatype to a literal of that
+  /// datatype, and emits the re-bound variable in the template instead.
+  ///
+  /// Values stored with another datatype, such as `xsd:integer` for a `u8`
+  /// field, are then returned with the datatype expected by
+  /// `linked_data_next`. Stores that canonicalise derived XSD types back into
+  /// `xsd:integer` also do so in query results, so the coercion takes two
+  /// steps: keep the [`template_datatypes`](Self::template_datatypes) of the
+  /// coerced query before running it, then give them to
+  /// [`restore_datatypes`](crate::restore_datatypes) along with the results.
+  /// [`Repository`](crate::Repository) does both.
+  pub fn coerce_datatypes(mut self) -> Self {
+    let mut coerced_datatypes = Vec::new();
+
+    for (variable, datatype) in &self.datatypes {
+      let coerced = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
+      coerced_datatypes.push((coerced.clone(), datatype.clone()));
+
+      for pattern in &mut self.construct_template {
+        if pattern.object == TermPattern::Variable(variable.clone()) {
+          pattern.object = coerced.clone().into();
+        }
+      }
+
+      self.where_pattern = GraphPattern::Extend {
+        inner: Box::new(self.where_pattern),
+        variable: coerced,
+        expression: Expression::FunctionCall(
+          Function::StrDt,
+          vec![
+            Expression::FunctionCall(Function::Str, vec![Expression::Variable(variable.clone())]),
+            Expression::NamedNode(datatype.clone()),
+          ],
+        ),
+      };
+    }
+
+    self.datatypes.extend(coerced_datatypes);
+    self
+  }
+
+  /// Returns the template along with the datatype recorded for its
+  /// variables, to be given to [`restore_datatypes`](crate::restore_datatypes)
+  /// once the query has run.
+  pub fn template_datatypes(&self) -> TemplateDatatypes {
+    TemplateDatatypes::new(self.construct_template.clone(), self.datatypes.clone())
+  }
+
+  /// Returns the template along with the optimized where pattern.
+  pub(crate) fn into_parts(mut self) -> (Vec<TriplePattern>, GraphPattern) {
+    self.apply_constraints();
+    let pattern = (&Optimizer::optimize_graph_pattern((&self.where_pattern).into())).into();
+
+    (self.construct_template, pattern)
+  }
+}
+
+/// The triples and property paths of `pattern`, as links from their subject
+/// to their object.
+fn edges(pattern: &GraphPattern) -> Vec<(TermPattern, PropertyPathExpression, TermPattern)> {
+  match pattern {
+    GraphPattern::Bgp { patterns } => patterns
+      .iter()
+      .filter_map(|pattern| match &pattern.predicate {
+        NamedNodePattern::NamedNode(predicate) => Some((
+          pattern.subject.clone(),
+          PropertyPathExpression::NamedNode(predicate.clone()),
+          pattern.object.clone(),
+        )),
+        NamedNodePattern::Variable(_) => None,
+      })
+      .collect(),
+    GraphPattern::Path {
+      subject,
+      path,
+      object,
+    } => vec![(subject.clone(), path.clone(), object.clone())],
+    GraphPattern::Join { left, right }
+    | GraphPattern::LeftJoin { left, right, .. }
+    | GraphPattern::Union { left, right } => {
+      let mut links = edges(left);
+      links.extend(edges(right));
+      links
+    }
+    GraphPattern::Minus { left, .. } => edges(left),
+    GraphPattern::Filter { inner, .. }
+    | GraphPattern::Graph { inner, .. }
+    | GraphPattern::Extend { inner, .. }
+    | GraphPattern::OrderBy { inner, .. }
+    | GraphPattern::Project { inner, .. }
+    | GraphPattern::Distinct { inner }
+    | GraphPattern::Reduced { inner }
+    | GraphPattern::Slice { inner, .. }
+    | GraphPattern::Group { inner, .. } => edges(inner),
+    _ => Vec::new(),
+  }
+}
+
+/// The sequence of `edges` leading from `subject` to `object`, if any.
+fn link(
+  edges: &[(TermPattern, PropertyPathExpression, TermPattern)],
+  subject: &Variable,
+  object: &Variable,
+) -> Option<PropertyPathExpression> {
+  let mut paths = vec![(
+    TermPattern::from(subject.clone()),
+    None::<PropertyPathExpression>,
+  )];
+  let mut visited = vec![TermPattern::from(subject.clone())];
+
+  while !paths.is_empty() {
+    let mut next = Vec::new();
+
+    for (term, path) in paths {
+      for (_, step, to) in edges.iter().filter(|(from, _, _)| *from == term) {
+        if visited.contains(to) {
+          continue;
+        }
+
+        let path = match &path {
+          None => step.clone(),
+          Some(path) => {
+            PropertyPathExpression::Sequence(Box::new(path.clone()), Box::new(step.clone()))
+          }
+        };
+
+        if *to == TermPattern::from(object.clone()) {
+          return Some(path);
+        }
+
+        visited.push(to.clone());
+        next.push((to.clone(), Some(path)));
+      }
+    }
+
+    paths = next;
+  }
+
+  None
+}
+
+/// Filters `pattern` on each condition, for the solutions binding its
+/// variable.
+fn filter_bound(pattern: GraphPattern, conditions: &[(Variable, Expression)]) -> GraphPattern {
+  let expr = conditions
+    .iter()
+    .map(|(variable, condition)| {
+      Expression::Or(
+        Box::new(Expression::Not(Box::new(Expression::Bound(
+          variable.clone(),
+        )))),
+        Box::new(condition.clone()),
+      )
+    })
+    .reduce(|left, right| Expression::And(Box::new(left), Box::new(right)));
+
+  match expr {
+    Some(expr) => GraphPattern::Filter {
+      expr,
+      inner: Box::new(pattern),
+    },
+    None => pattern,
+  }
+}
+
+/// Filters the right side of every optional pattern on the `guards` of the
+/// variables it binds, innermost first, and removes them from `guards`.
+fn place_guards(pattern: GraphPattern, guards: &mut Vec<(Variable, Expression)>) -> GraphPattern {
+  match pattern {
+    GraphPattern::LeftJoin {
+      left,
+      right,
+      expression,
+    } => {
+      let right = place_guards(*right, guards);
+      let left = place_guards(*left, guards);
+
+      let mut bound = Vec::new();
+      right.on_in_scope_variable(|variable| bound.push(variable.clone()));
+      let (inner, outer) = std::mem::take(guards)
+        .into_iter()
+        .partition::<Vec<_>, _>(|(variable, _)| bound.contains(variable));
+      *guards = outer;
+
+      GraphPattern::LeftJoin {
+        left: Box::new(left),
+        right: Box::new(filter_bound(right, &inner)),
+        expression,
+      }
+    }
+    GraphPattern::Join { left, right } => GraphPattern::Join {
+      left: Box::new(place_guards(*left, guards)),
+      right: Box::new(place_guards(*right, guards)),
+    },
+    GraphPattern::Union { left, right } => GraphPattern::Union {
+      left: Box::new(place_guards(*left, guards)),
+      right: Box::new(place_guards(*right, guards)),
+    },
+    GraphPattern::Filter { expr, inner } => GraphPattern::Filter {
+      expr,
+      inner: Box::new(place_guards(*inner, guards)),
+    },
+    GraphPattern::Extend {
+      inner,
+      variable,
+      expression,
+    } => GraphPattern::Extend {
+      inner: Box::new(place_guards(*inner, guards)),
+      variable,
+      expression,
+    },
+    pattern => pattern,
+  }
+}
+
+/// `isLiteral(?variable) && datatype(?variable) IN (...)`, also accepting
+/// `xsd:integer` for derived integer datatypes.
+fn datatype_guard(variable: &Variable, datatype: &NamedNode) -> Expression {
+  let mut datatypes = vec![Expression::NamedNode(datatype.clone())];
+
+  if xsd::DERIVED_INTEGERS.contains(&datatype.as_str()) {
+    datatypes.push(Expression::NamedNode(NamedNode::new_unchecked(
+      xsd::INTEGER,
+    )));
+  }
+
+  Expression::And(
+    Box::new(Expression::FunctionCall(
+      Function::IsLiteral,
+      vec![Expression::Variable(variable.clone())],
+    )),
+    Box::new(Expression::In(
+      Box::new(Expression::FunctionCall(
+        Function::Datatype,
+        vec![Expression::Variable(variable.clone())],
+      )),
+      datatypes,
+    )),
+  )
+}
+
+/// Whether the value of `other` is kept rather than the value of `object`:
+/// the one in the first preferred language, then the smallest value and
+/// language tag, so a single value is kept in each language.
+fn precedes(other: &Variable, object: &Variable, languages: &[&str]) -> Expression {
+  let rank = |variable| language_rank(variable, languages);
+  let string = |function, variable: &Variable| {
+    Expression::FunctionCall(function, vec![Expression::Variable(variable.clone())])
+  };
+
+  [
+    (rank(other), rank(object)),
+    (string(Function::Str, other), string(Function::Str, object)),
+    (
+      string(Function::Lang, other),
+      string(Function::Lang, object),
+    ),
+  ]
+  .into_iter()
+  .rev()
+  .fold(
+    Expression::Literal(Literal::from(false)),
+    |tie, (left, right)| {
+      Expression::Or(
+        Box::new(Expression::Less(
+          Box::new(left.clone()),
+          Box::new(right.clone()),
+        )),
+        Box::new(Expression::And(
+          Box::new(Expression::Equal(Box::new(left), Box::new(right))),
+          Box::new(tie),
+        )),
+      )
+    },
+  )
+}
+
+/// The position of the language of `variable` in `languages`, followed by
+/// untagged values and then by any other language.
+fn language_rank(variable: &Variable, languages: &[&str]) -> Expression {
+  let language =
+    Expression::FunctionCall(Function::Lang, vec![Expression::Variable(variable.clone())]);
+
+  let untagged = Expression::If(
+    Box::new(Expression::Equal(
+      Box::new(language.clone()),
+      Box::new(Expression::Literal(Literal::new_simple_literal(""))),
+    )),
+    Box::new(Expression::Literal(Literal::from(languages.len() as i64))),
+    Box::new(Expression::Literal(Literal::from(
+      languages.len() as i64 + 1,
+    ))),
+  );
+
+  languages
+    .iter()
+    .enumerate()
+    .rev()
+    .fold(untagged, |fallback, (rank, tag)| {
+      Expression::If(
+        Box::new(Expression::FunctionCall(
+          Function::LangMatches,
+          vec![
+            language.clone(),
+            Expression::Literal(Literal::new_simple_literal(*tag)),
+          ],
+        )),
+        Box::new(Expression::Literal(Literal::from(rank as i64))),
+        Box::new(fallback),
+      )
+    })
 }
 
 impl From<ConstructQuery> for Query {
   fn from(value: ConstructQuery) -> Self {
-    let pattern = (&Optimizer::optimize_graph_pattern((&value.where_pattern).into())).into();
+    let (template, pattern) = value.into_parts();
 
     Query::Construct {
-      template: value.construct_template,
+      template,
       dataset: None,
       pattern,
       base_iri: None,
@@ -115,6 +1101,16 @@ impl ToConstructQuery for Variable {
 }
 
 macro_rules! to_construct_query_datatypes {
+    ($($t:ty => $datatype:expr),*) => {
+        $(
+            impl ToConstructQuery for $t {
+                fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+                    ConstructQuery::default()
+                        .with_datatype(binding_variable, NamedNode::new_unchecked($datatype))
+                }
+            }
+        )*
+    };
     ($($t:ty),*) => {
         $(
             impl ToConstructQuery for $t {
@@ -127,22 +1123,139 @@ macro_rules! to_construct_query_datatypes {
 }
 
 to_construct_query_datatypes!(
-  u8,
-  u16,
-  u32,
-  u64,
-  i8,
-  i16,
-  i32,
-  i64,
-  String,
-  xsd_types::DateTime
+  bool => xsd::BOOLEAN,
+  u8 => xsd::UNSIGNED_BYTE,
+  u16 => xsd::UNSIGNED_SHORT,
+  u32 => xsd::UNSIGNED_INT,
+  u64 => xsd::UNSIGNED_LONG,
+  i8 => xsd::BYTE,
+  i16 => xsd::SHORT,
+  i32 => xsd::INT,
+  i64 => xsd::LONG,
+  f32 => xsd::FLOAT,
+  f64 => xsd::DOUBLE,
+  xsd_types::Date => xsd::DATE,
+  xsd_types::DateTime => xsd::DATE_TIME,
+  xsd_types::Decimal => xsd::DECIMAL,
+  xsd_types::Duration => xsd::DURATION,
+  xsd_types::Time => xsd::TIME
 );
 
+impl ToConstructQuery for String {
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    ConstructQuery::default()
+      .with_datatype(
+        binding_variable.clone(),
+        NamedNode::new_unchecked(xsd::STRING),
+      )
+      .with_language_tags(binding_variable)
+  }
+}
+
+to_construct_query_datatypes!(u128, usize, i128, isize, char, rdf_types::Term);
+
+impl ToConstructQuery for iref::IriBuf {
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    let guard = Expression::FunctionCall(
+      Function::IsIri,
+      vec![Expression::Variable(binding_variable.clone())],
+    );
+
+    ConstructQuery::default().with_guard(binding_variable, guard)
+  }
+}
+
+impl ToConstructQuery for rdf_types::Id {
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    let guard = Expression::Not(Box::new(Expression::FunctionCall(
+      Function::IsLiteral,
+      vec![Expression::Variable(binding_variable.clone())],
+    )));
+
+    ConstructQuery::default().with_guard(binding_variable, guard)
+  }
+}
+
+impl ToConstructQuery for rdf_types::Literal {
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    let guard = Expression::FunctionCall(
+      Function::IsLiteral,
+      vec![Expression::Variable(binding_variable.clone())],
+    );
+
+    ConstructQuery::default()
+      .with_guard(binding_variable.clone(), guard)
+      .with_language_tags(binding_variable)
+  }
+}
+
+macro_rules! to_construct_query_forwarding {
+    ($($t:ident),*) => {
+        $(
+            impl<T> ToConstructQuery for $t<T>
+            where
+                T: ?Sized + ToConstructQuery,
+            {
+                fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+                    T::to_query_with_binding(binding_variable)
+                }
+            }
+        )*
+    };
+}
+
+to_construct_query_forwarding!(Box, Rc, Arc);
+
+impl<T> ToConstructQuery for Cow<'_, T>
+where
+  T: ?Sized + ToOwned + ToConstructQuery,
+{
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    T::to_query_with_binding(binding_variable)
+  }
+}
+
+impl<T> ToConstructQuery for Option<T>
+where
+  T: ToConstructQuery,
+{
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    T::to_query_with_binding(binding_variable).optional()
+  }
+}
+
+impl<T> ToConstructQuery for Vec<T>
+where
+  T: ToConstructQuery,
+{
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    T::to_query_with_binding(binding_variable).optional()
+  }
+}
+
+impl ToConstructQuery for str {
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    String::to_query_with_binding(binding_variable)
+  }
+}
+
 impl Join for ConstructQuery {
   fn join(mut self, other: Self) -> Self {
     self.construct_template.extend(other.construct_template);
-    self.where_pattern = self.where_pattern.join(other.where_pattern);
+    self.where_pattern = if other.optional {
+      GraphPattern::LeftJoin {
+        left: Box::new(self.where_pattern),
+        right: Box::new(other.where_pattern),
+        expression: None,
+      }
+    } else {
+      self.where_pattern.join(other.where_pattern)
+    };
+    self.datatypes.extend(other.datatypes);
+    self.guards.extend(other.guards);
+    self.tagged.extend(other.tagged);
+    self.language_hops.extend(other.language_hops);
+    self.constraints.extend(other.constraints);
     self
   }
 }
@@ -151,6 +1264,11 @@ impl Union for ConstructQuery {
   fn union(mut self, other: Self) -> Self {
     self.construct_template.extend(other.construct_template);
     self.where_pattern = self.where_pattern.union(other.where_pattern);
+    self.datatypes.extend(other.datatypes);
+    self.guards.extend(other.guards);
+    self.tagged.extend(other.tagged);
+    self.language_hops.extend(other.language_hops);
+    self.constraints.extend(other.constraints);
     self
   }
 }
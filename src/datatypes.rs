@@ -0,0 +1,186 @@
+use crate::roots::ROOT_PREDICATE;
+use crate::xsd;
+use iref::IriBuf;
+use rdf_types::dataset::IndexedBTreeDataset;
+use rdf_types::{Id, Literal, LiteralType, Quad, Term};
+use spargebra::term::{NamedNode, NamedNodePattern, TermPattern, TriplePattern, Variable};
+use std::collections::{BTreeSet, HashMap};
+
+/// The template of a query along with the datatype recorded for its
+/// variables, as returned by
+/// [`ConstructQuery::template_datatypes`](crate::ConstructQuery::template_datatypes).
+#[derive(Clone, Debug, Default)]
+pub struct TemplateDatatypes {
+  template: Vec<TriplePattern>,
+  datatypes: Vec<(Variable, NamedNode)>,
+}
+
+impl TemplateDatatypes {
+  pub(crate) fn new(template: Vec<TriplePattern>, datatypes: Vec<(Variable, NamedNode)>) -> Self {
+    Self {
+      template,
+      datatypes,
+    }
+  }
+
+  /// Binds the variables of the template to the terms of `quads`, from the
+  /// subjects marked as roots, or else the subjects that are the object of
+  /// no triple.
+  fn bind(&self, quads: &[Quad]) -> HashMap<Variable, BTreeSet<Term>> {
+    let mut bindings = HashMap::<Variable, BTreeSet<Term>>::new();
+
+    let objects = self
+      .template
+      .iter()
+      .filter_map(|pattern| variable(&pattern.object))
+      .collect::<Vec<_>>();
+
+    let marked = quads
+      .iter()
+      .filter(|Quad(_, predicate, _, _)| is_iri(predicate, ROOT_PREDICATE))
+      .map(|Quad(subject, _, _, _)| subject.clone())
+      .collect::<BTreeSet<_>>();
+
+    let roots = if marked.is_empty() {
+      quads
+        .iter()
+        .map(|Quad(subject, _, _, _)| subject)
+        .filter(|subject| !quads.iter().any(|Quad(_, _, object, _)| object == *subject))
+        .cloned()
+        .collect()
+    } else {
+      marked
+    };
+
+    for subject in self
+      .template
+      .iter()
+      .filter_map(|pattern| variable(&pattern.subject))
+      .filter(|subject| !objects.contains(subject))
+    {
+      bindings.insert(subject.clone(), roots.clone());
+    }
+
+    let mut changed = true;
+    while changed {
+      changed = false;
+
+      for pattern in &self.template {
+        let (Some(predicate), Some(object)) = (iri(&pattern.predicate), variable(&pattern.object))
+        else {
+          continue;
+        };
+
+        let values = quads
+          .iter()
+          .filter(|Quad(subject, quad_predicate, _, _)| {
+            is_iri(quad_predicate, predicate) && matches(&bindings, &pattern.subject, subject)
+          })
+          .map(|Quad(_, _, value, _)| value.clone())
+          .collect::<Vec<_>>();
+
+        let bound = bindings.entry(object.clone()).or_default();
+        for value in values {
+          changed |= bound.insert(value);
+        }
+      }
+    }
+
+    bindings
+  }
+
+  /// The derived integer datatype of the template triple matching `quad`.
+  fn datatype(&self, bindings: &HashMap<Variable, BTreeSet<Term>>, quad: &Quad) -> Option<&str> {
+    let Quad(subject, predicate, _, _) = quad;
+
+    self
+      .template
+      .iter()
+      .filter(|pattern| iri(&pattern.predicate).is_some_and(|iri| is_iri(predicate, iri)))
+      .filter(|pattern| matches(bindings, &pattern.subject, subject))
+      .filter_map(|pattern| variable(&pattern.object))
+      .find_map(|object| {
+        self
+          .datatypes
+          .iter()
+          .find(|(variable, _)| variable == object)
+          .map(|(_, datatype)| datatype.as_str())
+      })
+      .filter(|datatype| xsd::DERIVED_INTEGERS.contains(datatype))
+  }
+}
+
+/// Gives back their derived integer datatype to the `xsd:integer` values of
+/// `dataset`, following the template triple each value was built from.
+///
+/// Stores that canonicalise derived integer literals, such as oxigraph, return
+/// `xsd:integer` even for values cast in the query, while `linked_data_next`
+/// only deserializes the exact datatype of each Rust integer type.
+///
+/// The template triples are told apart by the subjects they were bound to,
+/// walking the dataset from its roots, so a predicate used by several nested
+/// types with different datatypes gets back the datatype of each.
+pub fn restore_datatypes(
+  dataset: IndexedBTreeDataset,
+  datatypes: &TemplateDatatypes,
+) -> IndexedBTreeDataset {
+  let quads = dataset.into_iter().collect::<Vec<_>>();
+  let bindings = datatypes.bind(&quads);
+
+  quads
+    .iter()
+    .map(
+      |quad| match (&quad.2, datatypes.datatype(&bindings, quad)) {
+        (Term::Literal(literal), Some(datatype)) => Quad(
+          quad.0.clone(),
+          quad.1.clone(),
+          Term::Literal(restore_datatype(literal.clone(), datatype)),
+          quad.3.clone(),
+        ),
+        _ => quad.clone(),
+      },
+    )
+    .collect()
+}
+
+fn restore_datatype(literal: Literal, datatype: &str) -> Literal {
+  match (IriBuf::new(datatype.to_owned()), &literal.type_) {
+    (Ok(datatype), LiteralType::Any(iri)) if iri.as_str() == xsd::INTEGER => {
+      Literal::new(literal.value, LiteralType::Any(datatype))
+    }
+    _ => literal,
+  }
+}
+
+/// Whether `term` is one of the terms bound to the template term `pattern`.
+fn matches(
+  bindings: &HashMap<Variable, BTreeSet<Term>>,
+  pattern: &TermPattern,
+  term: &Term,
+) -> bool {
+  match pattern {
+    TermPattern::Variable(variable) => bindings
+      .get(variable)
+      .is_some_and(|bound| bound.contains(term)),
+    TermPattern::NamedNode(node) => is_iri(term, node.as_str()),
+    _ => false,
+  }
+}
+
+fn variable(pattern: &TermPattern) -> Option<&Variable> {
+  match pattern {
+    TermPattern::Variable(variable) => Some(variable),
+    _ => None,
+  }
+}
+
+fn iri(pattern: &NamedNodePattern) -> Option<&str> {
+  match pattern {
+    NamedNodePattern::NamedNode(node) => Some(node.as_str()),
+    NamedNodePattern::Variable(_) => None,
+  }
+}
+
+fn is_iri(term: &Term, expected: &str) -> bool {
+  matches!(term, Term::Id(Id::Iri(iri)) if iri.as_str() == expected)
+}
@@ -0,0 +1,66 @@
+use linked_data_next::{FromLinkedDataError, IntoQuadsError};
+use std::fmt;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug)]
+pub enum Error {
+  /// The backend could not execute the request.
+  Transport(BoxError),
+  /// The backend answered with a payload that could not be parsed.
+  Parse(BoxError),
+  /// The backend answered with results that do not match the query form.
+  UnexpectedResults,
+  /// The returned graph could not be deserialized into the requested type.
+  Deserialize(FromLinkedDataError),
+  /// The value could not be serialized into RDF.
+  Serialize(IntoQuadsError),
+  /// The generated pattern cannot be turned into the requested operation.
+  Unsupported(String),
+}
+
+impl Error {
+  pub fn transport(error: impl Into<BoxError>) -> Self {
+    Self::Transport(error.into())
+  }
+
+  pub fn parse(error: impl Into<BoxError>) -> Self {
+    Self::Parse(error.into())
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Transport(error) => write!(f, "transport error: {error}"),
+      Self::Parse(error) => write!(f, "parse error: {error}"),
+      Self::UnexpectedResults => f.write_str("unexpected query results"),
+      Self::Deserialize(error) => write!(f, "deserialization error: {error}"),
+      Self::Serialize(error) => write!(f, "serialization error: {error}"),
+      Self::Unsupported(reason) => write!(f, "unsupported operation: {reason}"),
+    }
+  }
+}
+
+impl std::error::Error for Error {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Self::Transport(error) | Self::Parse(error) => Some(error.as_ref()),
+      Self::Deserialize(error) => Some(error),
+      Self::Serialize(error) => Some(error),
+      Self::UnexpectedResults | Self::Unsupported(_) => None,
+    }
+  }
+}
+
+impl From<FromLinkedDataError> for Error {
+  fn from(error: FromLinkedDataError) -> Self {
+    Self::Deserialize(error)
+  }
+}
+
+impl From<IntoQuadsError> for Error {
+  fn from(error: IntoQuadsError) -> Self {
+    Self::Serialize(error)
+  }
+}
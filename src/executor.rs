@@ -0,0 +1,36 @@
+use crate::Error;
+use rdf_types::dataset::IndexedBTreeDataset;
+use sparesults::QuerySolution;
+use spargebra::{Query, Update};
+
+/// A SPARQL backend able to evaluate generated queries and updates.
+pub trait Executor {
+  fn construct(&self, query: Query) -> Result<IndexedBTreeDataset, Error>;
+
+  fn select(&self, query: Query) -> Result<Vec<QuerySolution>, Error>;
+
+  fn ask(&self, query: Query) -> Result<bool, Error>;
+
+  fn update(&self, update: Update) -> Result<(), Error>;
+}
+
+impl<E> Executor for &E
+where
+  E: Executor + ?Sized,
+{
+  fn construct(&self, query: Query) -> Result<IndexedBTreeDataset, Error> {
+    (**self).construct(query)
+  }
+
+  fn select(&self, query: Query) -> Result<Vec<QuerySolution>, Error> {
+    (**self).select(query)
+  }
+
+  fn ask(&self, query: Query) -> Result<bool, Error> {
+    (**self).ask(query)
+  }
+
+  fn update(&self, update: Update) -> Result<(), Error> {
+    (**self).update(update)
+  }
+}
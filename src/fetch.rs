@@ -0,0 +1,34 @@
+use crate::roots::first_root;
+use crate::{Error, Executor, SparqlQuery, extract_roots};
+use linked_data_next::LinkedDataDeserializeSubject;
+use spargebra::Query;
+
+/// Runs the generated query of a type and deserializes every root resource of
+/// the result.
+pub trait Fetch: SparqlQuery + LinkedDataDeserializeSubject {
+  fn fetch_all(executor: &impl Executor) -> Result<Vec<Self>, Error> {
+    fetch(executor, Self::sparql_algebra_with_roots())
+  }
+
+  /// Fetches the root resource with the smallest IRI, the query only
+  /// matching that one resource.
+  fn fetch_one(executor: &impl Executor) -> Result<Option<Self>, Error> {
+    let resources = fetch(executor, first_root(Self::sparql_algebra_with_roots()))?;
+
+    Ok(resources.into_iter().next())
+  }
+}
+
+impl<T> Fetch for T where T: SparqlQuery + LinkedDataDeserializeSubject {}
+
+fn fetch<T: LinkedDataDeserializeSubject>(
+  executor: &impl Executor,
+  query: Query,
+) -> Result<Vec<T>, Error> {
+  let (roots, dataset) = extract_roots(executor.construct(query)?)?;
+
+  roots
+    .iter()
+    .map(|root| Ok(T::deserialize_subject(&(), &(), &dataset, None, root)?))
+    .collect()
+}
@@ -0,0 +1,138 @@
+use crate::{Error, Executor, IntoRdfTypes};
+use oxttl::{NTriplesParser, TurtleParser};
+use rdf_types::dataset::IndexedBTreeDataset;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use sparesults::{
+  QueryResultsFormat, QueryResultsParser, QuerySolution, ReaderQueryResultsParserOutput,
+};
+use spargebra::term::Triple;
+use spargebra::{Query, Update};
+
+pub(crate) const SPARQL_QUERY: &str = "application/sparql-query";
+pub(crate) const SPARQL_UPDATE: &str = "application/sparql-update";
+pub(crate) const SPARQL_RESULTS_JSON: &str = "application/sparql-results+json";
+pub(crate) const N_TRIPLES: &str = "application/n-triples";
+pub(crate) const TURTLE: &str = "text/turtle";
+
+/// An [`Executor`] speaking the SPARQL 1.1 Protocol to a remote endpoint.
+pub struct HttpExecutor {
+  client: Client,
+  query_endpoint: String,
+  update_endpoint: String,
+}
+
+impl HttpExecutor {
+  pub fn new(query_endpoint: impl Into<String>, update_endpoint: impl Into<String>) -> Self {
+    Self {
+      client: Client::new(),
+      query_endpoint: query_endpoint.into(),
+      update_endpoint: update_endpoint.into(),
+    }
+  }
+
+  pub fn with_client(self, client: Client) -> Self {
+    Self { client, ..self }
+  }
+
+  fn post(
+    &self,
+    endpoint: &str,
+    content_type: &str,
+    accept: Option<&str>,
+    body: String,
+  ) -> Result<Response, Error> {
+    let mut request = self
+      .client
+      .post(endpoint)
+      .header(CONTENT_TYPE, content_type)
+      .body(body);
+
+    if let Some(accept) = accept {
+      request = request.header(ACCEPT, accept);
+    }
+
+    request
+      .send()
+      .and_then(Response::error_for_status)
+      .map_err(Error::transport)
+  }
+
+  fn query_results(&self, query: Query) -> Result<ReaderQueryResultsParserOutput<Response>, Error> {
+    let response = self.post(
+      &self.query_endpoint,
+      SPARQL_QUERY,
+      Some(SPARQL_RESULTS_JSON),
+      query.to_string(),
+    )?;
+
+    QueryResultsParser::from_format(QueryResultsFormat::Json)
+      .for_reader(response)
+      .map_err(Error::parse)
+  }
+}
+
+impl Executor for HttpExecutor {
+  fn construct(&self, query: Query) -> Result<IndexedBTreeDataset, Error> {
+    let response = self.post(
+      &self.query_endpoint,
+      SPARQL_QUERY,
+      Some(&format!("{N_TRIPLES}, {TURTLE};q=0.9")),
+      query.to_string(),
+    )?;
+
+    let is_turtle = response
+      .headers()
+      .get(CONTENT_TYPE)
+      .and_then(|value| value.to_str().ok())
+      .is_some_and(|value| value.starts_with(TURTLE));
+
+    if is_turtle {
+      to_dataset(TurtleParser::new().for_reader(response))
+    } else {
+      to_dataset(NTriplesParser::new().for_reader(response))
+    }
+  }
+
+  fn select(&self, query: Query) -> Result<Vec<QuerySolution>, Error> {
+    match self.query_results(query)? {
+      ReaderQueryResultsParserOutput::Solutions(solutions) => {
+        solutions.collect::<Result<_, _>>().map_err(Error::parse)
+      }
+      ReaderQueryResultsParserOutput::Boolean(_) => Err(Error::UnexpectedResults),
+    }
+  }
+
+  fn ask(&self, query: Query) -> Result<bool, Error> {
+    match self.query_results(query)? {
+      ReaderQueryResultsParserOutput::Boolean(value) => Ok(value),
+      ReaderQueryResultsParserOutput::Solutions(_) => Err(Error::UnexpectedResults),
+    }
+  }
+
+  fn update(&self, update: Update) -> Result<(), Error> {
+    self.post(
+      &self.update_endpoint,
+      SPARQL_UPDATE,
+      None,
+      update.to_string(),
+    )?;
+
+    Ok(())
+  }
+}
+
+fn to_dataset<E>(
+  triples: impl Iterator<Item = Result<Triple, E>>,
+) -> Result<IndexedBTreeDataset, Error>
+where
+  E: std::error::Error + Send + Sync + 'static,
+{
+  let mut dataset = IndexedBTreeDataset::new();
+
+  for triple in triples {
+    dataset.insert(triple.map_err(Error::parse)?.into_rdf_types()?);
+  }
+
+  Ok(dataset)
+}
@@ -0,0 +1,302 @@
+use crate::{ConstructQuery, ToConstructQuery, xsd};
+use langtag::LangTagBuf;
+use linked_data_next::{
+  Context, CowRdfTerm, FromLinkedDataError, LinkedDataDeserializePredicateObjects,
+  LinkedDataDeserializeSubject, LinkedDataPredicateObjects, LinkedDataResource, LinkedDataSubject,
+  PredicateObjectsVisitor, RdfLiteralRef, ResourceInterpretation, SubjectVisitor,
+};
+use rdf_types::dataset::PatternMatchingDataset;
+use rdf_types::interpretation::{ReverseIriInterpretation, ReverseLiteralInterpretation};
+use rdf_types::vocabulary::{IriVocabularyMut, LiteralVocabulary, LiteralVocabularyMut};
+use rdf_types::{Interpretation, LiteralTypeRef, Term, Vocabulary};
+use spargebra::algebra::{Expression, Function};
+use spargebra::term::{Literal, NamedNode, Variable};
+use std::collections::BTreeMap;
+use std::ops::{Deref, DerefMut};
+
+const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+
+/// A string that may carry a language tag, such as the values of
+/// `rdfs:label`.
+///
+/// The query matches every value of the property: other values, such as IRIs
+/// or typed literals, fail the deserialization unless the query filters them
+/// out with [`ConstructQuery::guard_leaves`], which applies the
+/// `xsd:string` or `rdf:langString` datatype guard.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LangString {
+  pub value: String,
+  pub language: Option<LangTagBuf>,
+}
+
+impl LangString {
+  pub fn new(value: String, language: Option<LangTagBuf>) -> Self {
+    Self { value, language }
+  }
+}
+
+/// The values of a multilingual property, such as `rdfs:label`, by language.
+///
+/// The query matches every value of the property, and the untagged strings
+/// are skipped when deserializing. Other values, such as IRIs or typed
+/// literals, fail the deserialization unless the query filters them out with
+/// [`ConstructQuery::guard_leaves`], which applies the `langMatches` guard.
+/// Each language must have a single value.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LangMap(BTreeMap<LangTagBuf, String>);
+
+impl LangMap {
+  pub fn new(values: BTreeMap<LangTagBuf, String>) -> Self {
+    Self(values)
+  }
+
+  pub fn into_inner(self) -> BTreeMap<LangTagBuf, String> {
+    self.0
+  }
+}
+
+impl Deref for LangMap {
+  type Target = BTreeMap<LangTagBuf, String>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl DerefMut for LangMap {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.0
+  }
+}
+
+impl From<BTreeMap<LangTagBuf, String>> for LangMap {
+  fn from(values: BTreeMap<LangTagBuf, String>) -> Self {
+    Self(values)
+  }
+}
+
+impl FromIterator<(LangTagBuf, String)> for LangMap {
+  fn from_iter<I: IntoIterator<Item = (LangTagBuf, String)>>(values: I) -> Self {
+    Self(values.into_iter().collect())
+  }
+}
+
+impl ToConstructQuery for LangString {
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    let guard = Expression::In(
+      Box::new(Expression::FunctionCall(
+        Function::Datatype,
+        vec![Expression::Variable(binding_variable.clone())],
+      )),
+      vec![
+        Expression::NamedNode(NamedNode::new_unchecked(xsd::STRING)),
+        Expression::NamedNode(NamedNode::new_unchecked(RDF_LANG_STRING)),
+      ],
+    );
+
+    ConstructQuery::default()
+      .with_guard(binding_variable.clone(), guard)
+      .with_language_tags(binding_variable)
+  }
+}
+
+impl ToConstructQuery for LangMap {
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    let guard = Expression::FunctionCall(
+      Function::LangMatches,
+      vec![
+        Expression::FunctionCall(
+          Function::Lang,
+          vec![Expression::Variable(binding_variable.clone())],
+        ),
+        Expression::Literal(Literal::new_simple_literal("*")),
+      ],
+    );
+
+    ConstructQuery::default()
+      .with_guard(binding_variable, guard)
+      .optional()
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary + IriVocabularyMut + LiteralVocabularyMut>
+  LinkedDataResource<I, V> for LangString
+{
+  fn interpretation(
+    &self,
+    _vocabulary: &mut V,
+    _interpretation: &mut I,
+  ) -> ResourceInterpretation<'_, I, V> {
+    let literal = match &self.language {
+      Some(language) => RdfLiteralRef::Any(&self.value, LiteralTypeRef::LangString(language)),
+      None => RdfLiteralRef::Xsd(xsd_types::ValueRef::String(&self.value)),
+    };
+
+    ResourceInterpretation::Uninterpreted(Some(CowRdfTerm::Borrowed(Term::Literal(literal))))
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary + IriVocabularyMut + LiteralVocabularyMut>
+  LinkedDataSubject<I, V> for LangString
+{
+  fn visit_subject<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: SubjectVisitor<I, V>,
+  {
+    serializer.end()
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary + IriVocabularyMut + LiteralVocabularyMut>
+  LinkedDataPredicateObjects<I, V> for LangString
+{
+  fn visit_objects<S>(&self, mut visitor: S) -> Result<S::Ok, S::Error>
+  where
+    S: PredicateObjectsVisitor<I, V>,
+  {
+    visitor.object(self)?;
+    visitor.end()
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary + IriVocabularyMut + LiteralVocabularyMut>
+  LinkedDataPredicateObjects<I, V> for LangMap
+{
+  fn visit_objects<S>(&self, mut visitor: S) -> Result<S::Ok, S::Error>
+  where
+    S: PredicateObjectsVisitor<I, V>,
+  {
+    for (language, value) in &self.0 {
+      visitor.object(&LangString::new(value.clone(), Some(language.clone())))?;
+    }
+    visitor.end()
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary> LinkedDataDeserializeSubject<I, V> for LangString
+where
+  V: LiteralVocabulary,
+  I: ReverseIriInterpretation<Iri = V::Iri> + ReverseLiteralInterpretation<Literal = V::Literal>,
+{
+  fn deserialize_subject_in<D>(
+    vocabulary: &V,
+    interpretation: &I,
+    _dataset: &D,
+    _graph: Option<&I::Resource>,
+    resource: &I::Resource,
+    context: Context<I>,
+  ) -> Result<Self, FromLinkedDataError>
+  where
+    D: PatternMatchingDataset<Resource = I::Resource>,
+  {
+    let mut found = None;
+    for literal in interpretation.literals_of(resource) {
+      let Some(literal) = vocabulary.literal(literal) else {
+        continue;
+      };
+
+      match literal.type_ {
+        LiteralTypeRef::LangString(language) => {
+          return Ok(Self::new(
+            literal.value.to_owned(),
+            Some(language.to_owned()),
+          ));
+        }
+        LiteralTypeRef::Any(datatype) => {
+          let datatype = vocabulary.iri(datatype).map(|iri| iri.to_owned());
+          if datatype
+            .as_ref()
+            .is_some_and(|iri| iri.as_str() == xsd::STRING)
+          {
+            return Ok(Self::new(literal.value.to_owned(), None));
+          }
+          found = datatype;
+        }
+      }
+    }
+
+    Err(match found {
+      Some(found) => FromLinkedDataError::LiteralTypeMismatch {
+        context: context.into_iris(vocabulary, interpretation),
+        expected: Some(xsd_types::XSD_STRING.to_owned()),
+        found,
+      },
+      None => FromLinkedDataError::ExpectedLiteral(context.into_iris(vocabulary, interpretation)),
+    })
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary> LinkedDataDeserializePredicateObjects<I, V> for LangString
+where
+  V: LiteralVocabulary,
+  I: ReverseIriInterpretation<Iri = V::Iri> + ReverseLiteralInterpretation<Literal = V::Literal>,
+{
+  fn deserialize_objects_in<'a, D>(
+    vocabulary: &V,
+    interpretation: &I,
+    dataset: &D,
+    graph: Option<&I::Resource>,
+    objects: impl IntoIterator<Item = &'a I::Resource>,
+    context: Context<I>,
+  ) -> Result<Self, FromLinkedDataError>
+  where
+    I::Resource: 'a,
+    D: PatternMatchingDataset<Resource = I::Resource>,
+  {
+    let mut objects = objects.into_iter();
+    match (objects.next(), objects.next()) {
+      (Some(object), None) => {
+        Self::deserialize_subject_in(vocabulary, interpretation, dataset, graph, object, context)
+      }
+      (None, _) => Err(FromLinkedDataError::MissingRequiredValue(
+        context.into_iris(vocabulary, interpretation),
+      )),
+      _ => Err(FromLinkedDataError::TooManyValues(
+        context.into_iris(vocabulary, interpretation),
+      )),
+    }
+  }
+}
+
+impl<I: Interpretation, V: Vocabulary> LinkedDataDeserializePredicateObjects<I, V> for LangMap
+where
+  V: LiteralVocabulary,
+  I: ReverseIriInterpretation<Iri = V::Iri> + ReverseLiteralInterpretation<Literal = V::Literal>,
+{
+  fn deserialize_objects_in<'a, D>(
+    vocabulary: &V,
+    interpretation: &I,
+    dataset: &D,
+    graph: Option<&I::Resource>,
+    objects: impl IntoIterator<Item = &'a I::Resource>,
+    context: Context<I>,
+  ) -> Result<Self, FromLinkedDataError>
+  where
+    I::Resource: 'a,
+    D: PatternMatchingDataset<Resource = I::Resource>,
+  {
+    let mut values = BTreeMap::new();
+
+    for object in objects {
+      let LangString { value, language } = LangString::deserialize_subject_in(
+        vocabulary,
+        interpretation,
+        dataset,
+        graph,
+        object,
+        context,
+      )?;
+      let Some(language) = language else {
+        continue;
+      };
+
+      if values.insert(language, value).is_some() {
+        return Err(FromLinkedDataError::TooManyValues(
+          context.into_iris(vocabulary, interpretation),
+        ));
+      }
+    }
+
+    Ok(Self(values))
+  }
+}
@@ -1,21 +1,56 @@
 mod and;
+#[cfg(feature = "async")]
+mod async_executor;
+#[cfg(all(feature = "async", feature = "http"))]
+mod async_http;
+mod collection;
 mod construct_query;
+mod datatypes;
+mod error;
+mod executor;
+mod fetch;
+#[cfg(feature = "http")]
+mod http;
 mod join;
+mod lang;
+mod rdf_type_conversions;
+mod reference;
+mod repository;
+mod roots;
 mod sparql_query;
 mod to_construct_query;
 mod union;
+mod xsd;
 
 pub use crate::and::And;
+#[cfg(feature = "async")]
+pub use crate::async_executor::{AsyncExecutor, SolutionStream, TripleStream, collect_dataset};
+#[cfg(all(feature = "async", feature = "http"))]
+pub use crate::async_http::AsyncHttpExecutor;
+pub use crate::collection::{List, Seq};
 pub use crate::construct_query::ConstructQuery;
+pub use crate::datatypes::{TemplateDatatypes, restore_datatypes};
+pub use crate::error::Error;
+pub use crate::executor::Executor;
+pub use crate::fetch::Fetch;
+#[cfg(feature = "http")]
+pub use crate::http::HttpExecutor;
 pub use crate::join::Join;
+pub use crate::lang::{LangMap, LangString};
+pub use crate::rdf_type_conversions::IntoRdfTypes;
+pub use crate::reference::Ref;
+pub use crate::repository::{Page, Repository};
+pub use crate::roots::{ROOT_PREDICATE, extract_roots};
 pub use crate::sparql_query::SparqlQuery;
-pub use crate::to_construct_query::ToConstructQuery;
+pub use crate::to_construct_query::{ToConstructQuery, expand_once};
 pub use crate::union::Union;
 pub use linked_data_sparql_derive::Sparql;
 use spargebra::Query;
 use spargebra::term::{NamedNode, Variable};
 
 pub mod reexport {
+  pub use rdf_types;
+  pub use sparesults;
   pub use spargebra;
 }
 
@@ -26,6 +61,10 @@ where
   fn sparql_algebra() -> Query {
     Self::to_query().into()
   }
+
+  fn sparql_algebra_with_roots() -> Query {
+    Self::to_query_with_roots().into()
+  }
 }
 
 pub fn with_predicate<F>(
@@ -35,9 +74,5 @@ pub fn with_predicate<F>(
 where
   F: FnOnce(Variable) -> ConstructQuery,
 {
-  |subject| {
-    let object = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
-
-    ConstructQuery::new(subject, predicate, object.clone()).join(to_query_with_binding(object))
-  }
+  |subject| ConstructQuery::new_with_binding(subject, predicate, to_query_with_binding)
 }
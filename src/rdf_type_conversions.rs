@@ -0,0 +1,102 @@
+use crate::Error;
+use std::str::FromStr;
+
+pub trait IntoRdfTypes: Sized {
+  type T;
+  fn into_rdf_types(self) -> Result<Self::T, Error>;
+}
+
+impl IntoRdfTypes for spargebra::term::Term {
+  type T = rdf_types::Term;
+
+  fn into_rdf_types(self) -> Result<Self::T, Error> {
+    Ok(match self {
+      spargebra::term::Term::NamedNode(id) => {
+        rdf_types::Term::Id(rdf_types::Id::Iri(id.into_rdf_types()?))
+      }
+      spargebra::term::Term::BlankNode(blank_node) => {
+        rdf_types::Term::Id(rdf_types::Id::Blank(blank_node.into_rdf_types()?))
+      }
+      spargebra::term::Term::Literal(literal) => {
+        rdf_types::Term::Literal(literal.into_rdf_types()?)
+      }
+      spargebra::term::Term::Triple(triple) => {
+        return Err(Error::parse(format!("unsupported quoted triple: {triple}")));
+      }
+    })
+  }
+}
+
+impl IntoRdfTypes for spargebra::term::Literal {
+  type T = rdf_types::Literal;
+
+  fn into_rdf_types(self) -> Result<Self::T, Error> {
+    let literal = self.to_string();
+
+    Ok(match self.destruct() {
+      (value, Some(datatype_iri), None) => rdf_types::Literal::new(
+        value,
+        rdf_types::LiteralType::Any(datatype_iri.into_rdf_types()?),
+      ),
+      (value, None, Some(language_tag)) => rdf_types::Literal::new(
+        value,
+        rdf_types::LiteralType::LangString(
+          langtag::LangTagBuf::from_str(&language_tag)
+            .map_err(|_| Error::parse(format!("invalid language tag: {language_tag}")))?,
+        ),
+      ),
+      (value, None, None) => rdf_types::Literal::new(
+        value,
+        rdf_types::LiteralType::Any(rdf_types::XSD_STRING.to_owned()),
+      ),
+      _ => return Err(Error::parse(format!("unsupported literal: {literal}"))),
+    })
+  }
+}
+
+impl IntoRdfTypes for spargebra::term::NamedNode {
+  type T = rdf_types::IriBuf;
+
+  fn into_rdf_types(self) -> Result<Self::T, Error> {
+    rdf_types::IriBuf::try_from(self.into_string())
+      .map_err(|error| Error::parse(format!("invalid IRI: {}", error.0)))
+  }
+}
+
+impl IntoRdfTypes for spargebra::term::BlankNode {
+  type T = rdf_types::BlankIdBuf;
+
+  fn into_rdf_types(self) -> Result<Self::T, Error> {
+    rdf_types::BlankIdBuf::new(format!("{}", self))
+      .map_err(|error| Error::parse(format!("invalid blank node identifier: {}", error.0)))
+  }
+}
+
+impl IntoRdfTypes for spargebra::term::Subject {
+  type T = rdf_types::Id;
+
+  fn into_rdf_types(self) -> Result<Self::T, Error> {
+    Ok(match self {
+      spargebra::term::Subject::NamedNode(id) => rdf_types::Id::Iri(id.into_rdf_types()?),
+      spargebra::term::Subject::BlankNode(blank_node) => {
+        rdf_types::Id::Blank(blank_node.into_rdf_types()?)
+      }
+      spargebra::term::Subject::Triple(triple) => {
+        return Err(Error::parse(format!("unsupported quoted triple: {triple}")));
+      }
+    })
+  }
+}
+
+impl IntoRdfTypes for spargebra::term::Triple {
+  type T = rdf_types::Quad;
+
+  fn into_rdf_types(self) -> Result<Self::T, Error> {
+    Ok(rdf_types::Quad(
+      self.subject.into_rdf_types()?.into_term(),
+      rdf_types::Id::Iri(self.predicate.into_rdf_types()?).into_term(),
+      self.object.into_rdf_types()?,
+      None,
+    ))
+  }
+}
@@ -0,0 +1,79 @@
+use crate::{ConstructQuery, Error, Executor, Repository, ToConstructQuery};
+use iref::{Iri, IriBuf};
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use spargebra::term::Variable;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A link to a resource of type `T` holding only its IRI.
+///
+/// Its pattern only matches the linking triple, so the linked resource is
+/// not fetched along with the resource holding the reference. It can be
+/// loaded later with [`Ref::load`].
+#[derive(Serialize, Deserialize)]
+pub struct Ref<T> {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld(ignore)]
+  resource: PhantomData<T>,
+}
+
+impl<T> Ref<T> {
+  pub fn new(id: IriBuf) -> Self {
+    Self {
+      id,
+      resource: PhantomData,
+    }
+  }
+
+  pub fn id(&self) -> &Iri {
+    &self.id
+  }
+
+  pub fn into_id(self) -> IriBuf {
+    self.id
+  }
+}
+
+impl<T> Ref<T>
+where
+  T: ToConstructQuery + LinkedDataDeserializeSubject,
+{
+  /// Fetches the referenced resource, if it exists.
+  pub fn load(&self, executor: &impl Executor) -> Result<Option<T>, Error> {
+    Repository::<T, _>::new(executor).get(&self.id)
+  }
+}
+
+impl<T> ToConstructQuery for Ref<T> {
+  fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery {
+    ConstructQuery::default().require_iri(binding_variable)
+  }
+}
+
+impl<T> Clone for Ref<T> {
+  fn clone(&self) -> Self {
+    Self::new(self.id.clone())
+  }
+}
+
+impl<T> PartialEq for Ref<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.id == other.id
+  }
+}
+
+impl<T> Eq for Ref<T> {}
+
+impl<T> fmt::Debug for Ref<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("Ref").field(&self.id).finish()
+  }
+}
+
+impl<T> From<IriBuf> for Ref<T> {
+  fn from(id: IriBuf) -> Self {
+    Self::new(id)
+  }
+}
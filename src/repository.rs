@@ -0,0 +1,311 @@
+use crate::roots::slice_roots;
+use crate::{
+  ConstructQuery, Error, Executor, Join, ROOT_PREDICATE, ToConstructQuery, extract_roots,
+  restore_datatypes,
+};
+use iref::Iri;
+use linked_data_next::{LinkedData, LinkedDataDeserializeSubject, RdfQuad, to_quads_with};
+use rdf_types::generator::Blank;
+use rdf_types::interpretation::WithGenerator;
+use spargebra::algebra::{
+  AggregateExpression, AggregateFunction, Expression, Function, GraphPattern,
+  PropertyPathExpression,
+};
+use spargebra::term::{
+  BlankNode, GraphName, GraphNamePattern, GroundQuadPattern, GroundTermPattern, Literal, NamedNode,
+  Quad, Subject, Term, TermPattern, TriplePattern, Variable,
+};
+use spargebra::{GraphUpdateOperation, Query, Update};
+use std::marker::PhantomData;
+
+/// A window over the resources of a [`Repository`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Page {
+  pub offset: usize,
+  pub limit: usize,
+}
+
+/// CRUD operations over the resources of type `T` stored behind an
+/// [`Executor`], all derived from the pattern generated for `T`.
+///
+/// Resources are addressed by IRI. Values rooted at a blank node can be
+/// listed, counted and saved, but every save inserts a new resource.
+pub struct Repository<T, E> {
+  executor: E,
+  resource: PhantomData<T>,
+}
+
+impl<T, E> Repository<T, E>
+where
+  T: ToConstructQuery + LinkedDataDeserializeSubject,
+  E: Executor,
+{
+  pub fn new(executor: E) -> Self {
+    Self {
+      executor,
+      resource: PhantomData,
+    }
+  }
+
+  pub fn get(&self, iri: &Iri) -> Result<Option<T>, Error> {
+    let query = Self::query_with_id(iri).mark_root(root());
+
+    Ok(self.deserialize(query)?.into_iter().next())
+  }
+
+  pub fn list(&self, page: Page) -> Result<Vec<T>, Error> {
+    let mut query = T::to_query_with_binding(root()).mark_root(root());
+    query.apply_constraints();
+
+    query.where_pattern = slice_roots(query.where_pattern, &root(), page.offset, page.limit);
+
+    self.deserialize(query)
+  }
+
+  pub fn count(&self) -> Result<usize, Error> {
+    let count = Variable::new_unchecked("count");
+    let (_, pattern) = T::to_query_with_binding(root()).into_parts();
+
+    let query = Query::Select {
+      dataset: None,
+      pattern: GraphPattern::Project {
+        inner: Box::new(GraphPattern::Group {
+          inner: Box::new(pattern),
+          variables: Vec::new(),
+          aggregates: vec![(
+            count.clone(),
+            AggregateExpression::FunctionCall {
+              name: AggregateFunction::Count,
+              expr: Expression::Variable(root()),
+              distinct: true,
+            },
+          )],
+        }),
+        variables: vec![count.clone()],
+      },
+      base_iri: None,
+    };
+
+    let solutions = self.executor.select(query)?;
+
+    match solutions.first().and_then(|solution| solution.get(&count)) {
+      Some(Term::Literal(literal)) => literal.value().parse().map_err(Error::parse),
+      _ => Err(Error::UnexpectedResults),
+    }
+  }
+
+  pub fn exists(&self, iri: &Iri) -> Result<bool, Error> {
+    let (_, pattern) = Self::query_with_id(iri).into_parts();
+
+    self.executor.ask(Query::Ask {
+      dataset: None,
+      pattern,
+      base_iri: None,
+    })
+  }
+
+  /// Inserts `value`, replacing the previous state of its root resource when
+  /// it is identified by an IRI.
+  pub fn save(&self, value: &T) -> Result<(), Error>
+  where
+    T: LinkedData<WithGenerator<Blank>>,
+  {
+    let mut interpretation = WithGenerator::new((), Blank::new());
+
+    let data = to_quads_with(&mut (), &mut interpretation, value)?
+      .into_iter()
+      .map(quad)
+      .collect::<Result<Vec<_>, _>>()?;
+
+    let mut operations = roots(&data)
+      .into_iter()
+      .flat_map(delete_operations)
+      .collect::<Vec<_>>();
+
+    operations.push(GraphUpdateOperation::InsertData { data });
+
+    self.executor.update(Update {
+      operations,
+      base_iri: None,
+    })
+  }
+
+  pub fn delete(&self, iri: &Iri) -> Result<(), Error> {
+    self.executor.update(Update {
+      operations: delete_operations(NamedNode::new_unchecked(iri.as_str())).to_vec(),
+      base_iri: None,
+    })
+  }
+
+  fn query_with_id(iri: &Iri) -> ConstructQuery {
+    T::to_query_with_binding(root()).filter_variable(root(), NamedNode::new_unchecked(iri.as_str()))
+  }
+
+  fn deserialize(&self, query: ConstructQuery) -> Result<Vec<T>, Error> {
+    let query = query.coerce_datatypes();
+    let datatypes = query.template_datatypes();
+    let dataset = restore_datatypes(self.executor.construct(query.into())?, &datatypes);
+    let (roots, dataset) = extract_roots(dataset)?;
+
+    roots
+      .iter()
+      .map(|root| Ok(T::deserialize_subject(&(), &(), &dataset, None, root)?))
+      .collect()
+  }
+}
+
+fn root() -> Variable {
+  Variable::new_unchecked("root")
+}
+
+/// IRI subjects of `data` that are not the object of any of its triples.
+fn roots(data: &[Quad]) -> Vec<NamedNode> {
+  let mut roots = data
+    .iter()
+    .filter_map(|quad| match &quad.subject {
+      Subject::NamedNode(subject) => Some(subject),
+      _ => None,
+    })
+    .filter(|subject| {
+      !data
+        .iter()
+        .any(|quad| matches!(&quad.object, Term::NamedNode(object) if object == *subject))
+    })
+    .cloned()
+    .collect::<Vec<_>>();
+
+  roots.sort();
+  roots.dedup();
+  roots
+}
+
+/// Deletes the triples of `id`, then those of the blank nodes it owns: the
+/// blank nodes reachable from `id` through no other IRI.
+///
+/// The blank nodes go first, as they are only found through the triples of
+/// `id`.
+fn delete_operations(id: NamedNode) -> [GraphUpdateOperation; 2] {
+  let subject = Variable::new_unchecked("subject");
+  let predicate = Variable::new_unchecked("predicate");
+  let object = Variable::new_unchecked("object");
+  let owner = Variable::new_unchecked("owner");
+
+  let reachable = |from: TermPattern, to: &Variable| GraphPattern::Path {
+    subject: from,
+    path: any_path(),
+    object: to.clone().into(),
+  };
+
+  let shared = GraphPattern::Filter {
+    expr: Expression::And(
+      Box::new(Expression::FunctionCall(
+        Function::IsIri,
+        vec![Expression::Variable(owner.clone())],
+      )),
+      Box::new(Expression::Not(Box::new(Expression::SameTerm(
+        Box::new(Expression::Variable(owner.clone())),
+        Box::new(Expression::NamedNode(id.clone())),
+      )))),
+    ),
+    inner: Box::new(
+      reachable(id.clone().into(), &owner).join(reachable(owner.clone().into(), &subject)),
+    ),
+  };
+
+  let owned = GraphPattern::Filter {
+    expr: Expression::And(
+      Box::new(Expression::FunctionCall(
+        Function::IsBlank,
+        vec![Expression::Variable(subject.clone())],
+      )),
+      Box::new(Expression::Not(Box::new(Expression::Exists(Box::new(
+        shared,
+      ))))),
+    ),
+    inner: Box::new(
+      reachable(id.clone().into(), &subject).join(GraphPattern::Bgp {
+        patterns: vec![TriplePattern {
+          subject: subject.clone().into(),
+          predicate: predicate.clone().into(),
+          object: object.clone().into(),
+        }],
+      }),
+    ),
+  };
+
+  let delete =
+    |subject: GroundTermPattern, pattern: GraphPattern| GraphUpdateOperation::DeleteInsert {
+      delete: vec![GroundQuadPattern {
+        subject,
+        predicate: predicate.clone().into(),
+        object: object.clone().into(),
+        graph_name: GraphNamePattern::DefaultGraph,
+      }],
+      insert: Vec::new(),
+      using: None,
+      pattern: Box::new(pattern),
+    };
+
+  let own = GraphPattern::Bgp {
+    patterns: vec![TriplePattern {
+      subject: id.clone().into(),
+      predicate: predicate.clone().into(),
+      object: object.clone().into(),
+    }],
+  };
+
+  [delete(subject.into(), owned), delete(id.into(), own)]
+}
+
+/// One or more triples of any predicate.
+fn any_path() -> PropertyPathExpression {
+  let any = NamedNode::new_unchecked(ROOT_PREDICATE);
+
+  PropertyPathExpression::OneOrMore(Box::new(PropertyPathExpression::Alternative(
+    Box::new(PropertyPathExpression::NamedNode(any.clone())),
+    Box::new(PropertyPathExpression::NegatedPropertySet(vec![any])),
+  )))
+}
+
+fn quad(rdf_types::Quad(subject, predicate, object, graph): RdfQuad) -> Result<Quad, Error> {
+  Ok(Quad {
+    subject: match subject {
+      rdf_types::Id::Iri(iri) => Subject::NamedNode(NamedNode::new_unchecked(iri.into_string())),
+      rdf_types::Id::Blank(blank) => Subject::BlankNode(blank_node(&blank)),
+    },
+    predicate: NamedNode::new_unchecked(predicate.into_string()),
+    object: match object {
+      rdf_types::Term::Id(rdf_types::Id::Iri(iri)) => {
+        Term::NamedNode(NamedNode::new_unchecked(iri.into_string()))
+      }
+      rdf_types::Term::Id(rdf_types::Id::Blank(blank)) => Term::BlankNode(blank_node(&blank)),
+      rdf_types::Term::Literal(literal) => Term::Literal(match literal.type_ {
+        rdf_types::LiteralType::Any(datatype) => Literal::new_typed_literal(
+          literal.value,
+          NamedNode::new_unchecked(datatype.into_string()),
+        ),
+        rdf_types::LiteralType::LangString(language) => {
+          Literal::new_language_tagged_literal_unchecked(
+            literal.value,
+            language.as_str().to_ascii_lowercase(),
+          )
+        }
+      }),
+    },
+    graph_name: match graph {
+      None => GraphName::DefaultGraph,
+      Some(rdf_types::Id::Iri(iri)) => {
+        GraphName::NamedNode(NamedNode::new_unchecked(iri.into_string()))
+      }
+      Some(rdf_types::Id::Blank(blank)) => {
+        return Err(Error::Unsupported(format!(
+          "the blank graph name {blank} cannot be inserted"
+        )));
+      }
+    },
+  })
+}
+
+fn blank_node(blank: &rdf_types::BlankId) -> BlankNode {
+  BlankNode::new_unchecked(blank.suffix())
+}
@@ -0,0 +1,112 @@
+use crate::{Error, Join};
+use rdf_types::dataset::IndexedBTreeDataset;
+use rdf_types::{Id, Quad, Term};
+use spargebra::Query;
+use spargebra::algebra::{Expression, GraphPattern, OrderExpression};
+use spargebra::term::{NamedNodePattern, TermPattern, TriplePattern, Variable};
+
+/// Predicate of the marker triple attached to every root resource by
+/// [`ConstructQuery::mark_root`](crate::ConstructQuery::mark_root).
+pub const ROOT_PREDICATE: &str = "urn:linked-data-sparql:root";
+
+/// Splits the marker triples off a CONSTRUCT result, returning the marked
+/// root resources along with the remaining dataset.
+///
+/// Fails with [`Error::UnexpectedResults`] when the result is not empty but
+/// marks no root, as for a query built without
+/// [`ConstructQuery::mark_root`](crate::ConstructQuery::mark_root).
+pub fn extract_roots(
+  dataset: IndexedBTreeDataset,
+) -> Result<(Vec<Term>, IndexedBTreeDataset), Error> {
+  let mut roots = Vec::new();
+  let mut remaining = IndexedBTreeDataset::new();
+
+  for Quad(subject, predicate, object, graph) in dataset.iter() {
+    if is_root_predicate(predicate) {
+      roots.push(subject.clone());
+    } else {
+      remaining.insert(Quad(
+        subject.clone(),
+        predicate.clone(),
+        object.clone(),
+        graph.cloned(),
+      ));
+    }
+  }
+
+  if roots.is_empty() && !remaining.is_empty() {
+    return Err(Error::UnexpectedResults);
+  }
+
+  roots.dedup();
+  Ok((roots, remaining))
+}
+
+/// Restricts `pattern` to the resources bound to `root` from `start` to
+/// `start + length`, in the order of their IRIs, with a sub-select.
+pub(crate) fn slice_roots(
+  pattern: GraphPattern,
+  root: &Variable,
+  start: usize,
+  length: usize,
+) -> GraphPattern {
+  let roots = GraphPattern::Slice {
+    inner: Box::new(GraphPattern::Distinct {
+      inner: Box::new(GraphPattern::Project {
+        inner: Box::new(GraphPattern::OrderBy {
+          inner: Box::new(pattern.clone()),
+          expression: vec![OrderExpression::Asc(Expression::Variable(root.clone()))],
+        }),
+        variables: vec![root.clone()],
+      }),
+    }),
+    start,
+    length: Some(length),
+  };
+
+  roots.join(pattern)
+}
+
+/// Restricts a CONSTRUCT `query` to its first root resource, when its
+/// template marks them.
+pub(crate) fn first_root(query: Query) -> Query {
+  let Query::Construct {
+    template,
+    dataset,
+    pattern,
+    base_iri,
+  } = query
+  else {
+    return query;
+  };
+
+  let pattern = match root_variable(&template) {
+    Some(root) => slice_roots(pattern, &root, 0, 1),
+    None => pattern,
+  };
+
+  Query::Construct {
+    template,
+    dataset,
+    pattern,
+    base_iri,
+  }
+}
+
+/// The variable marked as the root in `template`.
+fn root_variable(template: &[TriplePattern]) -> Option<Variable> {
+  template
+    .iter()
+    .find_map(|pattern| match (&pattern.subject, &pattern.predicate) {
+      (TermPattern::Variable(root), NamedNodePattern::NamedNode(predicate))
+        if predicate.as_str() == ROOT_PREDICATE =>
+      {
+        Some(root.clone())
+      }
+      _ => None,
+    })
+}
+
+fn is_root_predicate(predicate: &Term) -> bool {
+  matches!(predicate, Term::Id(Id::Iri(iri)) if iri.as_str() == ROOT_PREDICATE)
+}
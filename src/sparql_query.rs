@@ -11,6 +11,17 @@ pub trait SparqlQuery {
 
   fn sparql_algebra() -> Query;
 
+  /// Like [`SparqlQuery::sparql_algebra`], with the root resources marked in
+  /// the result for [`extract_roots`](crate::extract_roots).
+  ///
+  /// Defaults to [`SparqlQuery::sparql_algebra`], which marks no root: the
+  /// non-empty results of such a query are then rejected by
+  /// [`extract_roots`](crate::extract_roots) with
+  /// [`Error::UnexpectedResults`](crate::Error::UnexpectedResults).
+  fn sparql_algebra_with_roots() -> Query {
+    Self::sparql_algebra()
+  }
+
   fn as_sparql_algebra(&self) -> Query {
     Self::sparql_algebra()
   }
@@ -1,5 +1,6 @@
 use crate::ConstructQuery;
 use spargebra::term::Variable;
+use std::cell::RefCell;
 
 pub trait ToConstructQuery {
   fn to_query_with_binding(binding_variable: Variable) -> ConstructQuery;
@@ -9,4 +10,71 @@ pub trait ToConstructQuery {
 
     Self::to_query_with_binding(Variable::new_unchecked(object.into_string()))
   }
+
+  fn to_query_with_roots() -> ConstructQuery {
+    let root = Variable::new_unchecked(spargebra::term::BlankNode::default().into_string());
+
+    Self::to_query_with_binding(root.clone()).mark_root(root)
+  }
+}
+
+thread_local! {
+  /// The types whose pattern is being built, innermost last, along with the
+  /// variables bound to the resources of the same type nested in it.
+  static EXPANDING: RefCell<Vec<(&'static str, Vec<Variable>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Builds the pattern of `T` bound to `binding_variable` with `to_query`.
+///
+/// A resource of type `T` nested in the pattern of `T` itself, directly as
+/// with `Option<Box<Self>>` or through other types as with mutually
+/// recursive types, is not expanded again: it is only linked to, and the
+/// pattern of `T` is applied to every resource reachable from
+/// `binding_variable` through the patterns linking it to the nested
+/// resources.
+pub fn expand_once<T: ?Sized>(
+  binding_variable: Variable,
+  mut to_query: impl FnMut(Variable) -> ConstructQuery,
+) -> ConstructQuery {
+  struct Expanding;
+
+  impl Drop for Expanding {
+    fn drop(&mut self) {
+      EXPANDING.with(|expanding| expanding.borrow_mut().pop());
+    }
+  }
+
+  let name = std::any::type_name::<T>();
+  let expanded = EXPANDING.with(|expanding| {
+    let mut expanding = expanding.borrow_mut();
+    match expanding.iter_mut().find(|(expanded, _)| *expanded == name) {
+      Some((_, nested)) => {
+        nested.push(binding_variable.clone());
+        true
+      }
+      None => {
+        expanding.push((name, Vec::new()));
+        false
+      }
+    }
+  });
+  if expanded {
+    return ConstructQuery::default();
+  }
+
+  let _expanding = Expanding;
+  let query = to_query(binding_variable.clone());
+
+  let nested = EXPANDING.with(|expanding| {
+    expanding
+      .borrow_mut()
+      .last_mut()
+      .map(|(_, nested)| std::mem::take(nested))
+      .unwrap_or_default()
+  });
+  if nested.is_empty() {
+    return query;
+  }
+
+  query.recursive(binding_variable, &nested, to_query)
 }
@@ -0,0 +1,31 @@
+pub const BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+pub const BYTE: &str = "http://www.w3.org/2001/XMLSchema#byte";
+pub const DATE: &str = "http://www.w3.org/2001/XMLSchema#date";
+pub const DATE_TIME: &str = "http://www.w3.org/2001/XMLSchema#dateTime";
+pub const DECIMAL: &str = "http://www.w3.org/2001/XMLSchema#decimal";
+pub const DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+pub const DURATION: &str = "http://www.w3.org/2001/XMLSchema#duration";
+pub const FLOAT: &str = "http://www.w3.org/2001/XMLSchema#float";
+pub const INT: &str = "http://www.w3.org/2001/XMLSchema#int";
+pub const INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+pub const LONG: &str = "http://www.w3.org/2001/XMLSchema#long";
+pub const SHORT: &str = "http://www.w3.org/2001/XMLSchema#short";
+pub const STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+pub const TIME: &str = "http://www.w3.org/2001/XMLSchema#time";
+pub const UNSIGNED_BYTE: &str = "http://www.w3.org/2001/XMLSchema#unsignedByte";
+pub const UNSIGNED_INT: &str = "http://www.w3.org/2001/XMLSchema#unsignedInt";
+pub const UNSIGNED_LONG: &str = "http://www.w3.org/2001/XMLSchema#unsignedLong";
+pub const UNSIGNED_SHORT: &str = "http://www.w3.org/2001/XMLSchema#unsignedShort";
+
+/// Datatypes derived from `xsd:integer` that stores may canonicalise back to
+/// `xsd:integer`.
+pub const DERIVED_INTEGERS: [&str; 8] = [
+  BYTE,
+  SHORT,
+  INT,
+  LONG,
+  UNSIGNED_BYTE,
+  UNSIGNED_SHORT,
+  UNSIGNED_INT,
+  UNSIGNED_LONG,
+];
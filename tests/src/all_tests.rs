@@ -1,11 +1,35 @@
+mod test_aggregate;
+mod test_alias;
+mod test_async;
+mod test_bind;
+mod test_compile_fail;
 mod test_complex_struct;
 mod test_datatypes;
 mod test_enum;
 mod test_enum_blank_node;
+mod test_enum_struct_variant;
 mod test_enum_type;
+mod test_fetch;
+mod test_generics;
+mod test_guards;
+mod test_http;
+mod test_inverse;
+mod test_lang;
+mod test_list;
+mod test_optional;
+mod test_property_paths;
+mod test_recursive;
+mod test_ref;
+mod test_repository;
+mod test_roots;
+mod test_seq;
+mod test_sparql_with;
 mod test_struct;
 mod test_struct_flatten;
 mod test_struct_graph;
 mod test_struct_id;
+mod test_struct_tuple;
 mod test_struct_type;
+mod test_struct_types;
 mod test_struct_vec;
+mod test_wrappers;
@@ -0,0 +1,86 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, Sparql, ToConstructQuery, restore_datatypes};
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Account {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:name")]
+  name: String,
+
+  #[ld("ex:followerCount")]
+  #[sparql(count = "ex:follower")]
+  follower_count: u64,
+
+  #[ld("ex:total")]
+  #[sparql(sum = "ex:order/ex:amount")]
+  total: i64,
+
+  #[ld("ex:orderCustomers")]
+  #[sparql(count = "ex:order/ex:customer")]
+  order_customers: u64,
+
+  #[ld("ex:customerCount")]
+  #[sparql(count(distinct) = "ex:order/ex:customer")]
+  customer_count: u64,
+}
+
+#[test]
+fn test_aggregate() {
+  let store = TestGraphStore::new();
+
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://ex/shop> <http://ex/name> "Shop" ;
+        <http://ex/follower> <http://ex/ann>, <http://ex/bob> ;
+        <http://ex/order> <http://ex/first>, <http://ex/second> .
+      <http://ex/first> <http://ex/amount> 15 ; <http://ex/customer> <http://ex/ann> .
+      <http://ex/second> <http://ex/amount> 25 ; <http://ex/customer> <http://ex/ann> .
+      <http://ex/empty> <http://ex/name> "Empty" .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  // The store returns the `xsd:unsignedLong` and `xsd:long` values as
+  // `xsd:integer`.
+  let query = Account::to_query();
+  let datatypes = query.template_datatypes();
+  let dataset = restore_datatypes(store.query(query.into()), &datatypes);
+
+  let account = |id: &str| {
+    let id = IriBuf::new(id.to_owned()).unwrap();
+    let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(id);
+
+    Account::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap()
+  };
+
+  assert_eq!(
+    Account {
+      id: IriBuf::new("http://ex/shop".to_owned()).unwrap(),
+      name: "Shop".to_owned(),
+      follower_count: 2,
+      total: 40,
+      order_customers: 2,
+      customer_count: 1,
+    },
+    account("http://ex/shop")
+  );
+  assert_eq!(
+    Account {
+      id: IriBuf::new("http://ex/empty".to_owned()).unwrap(),
+      name: "Empty".to_owned(),
+      follower_count: 0,
+      total: 0,
+      order_customers: 0,
+      customer_count: 0,
+    },
+    account("http://ex/empty")
+  );
+}
@@ -0,0 +1,54 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, Sparql, SparqlQuery};
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("foaf" = "http://xmlns.com/foaf/0.1/"))]
+#[ld(prefix("schema" = "http://schema.org/"))]
+struct Person {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("foaf:name")]
+  #[sparql(alias = "schema:name")]
+  name: String,
+}
+
+#[test]
+fn test_alias() {
+  let store = TestGraphStore::new();
+
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://example.org/old> <http://schema.org/name> "Old" .
+      <http://example.org/new> <http://xmlns.com/foaf/0.1/name> "New" .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let dataset = store.query(Person::sparql_algebra());
+
+  assert_eq!(dataset.len(), 2);
+
+  for (id, name) in [
+    ("http://example.org/old", "Old"),
+    ("http://example.org/new", "New"),
+  ] {
+    let id = IriBuf::new(id.to_owned()).unwrap();
+    let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(id.clone());
+
+    let person = Person::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+    assert_eq!(
+      Person {
+        id,
+        name: name.to_owned(),
+      },
+      person
+    );
+  }
+}
@@ -0,0 +1,107 @@
+use crate::test_graph_store::TestGraphStore;
+use crate::test_http_server;
+use futures_util::TryStreamExt;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{AsyncExecutor, AsyncHttpExecutor, Sparql, SparqlQuery, collect_dataset};
+use oxigraph::io::RdfFormat;
+use rdf_types::Generator;
+use rdf_types::generator::Blank;
+use spargebra::{Query, Update};
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Struct {
+  #[ld("ex:field_0")]
+  field_0: String,
+
+  #[ld("ex:field_1")]
+  field_1: String,
+}
+
+fn executor(store: &TestGraphStore) -> AsyncHttpExecutor {
+  executor_as(store, RdfFormat::NTriples)
+}
+
+fn executor_as(store: &TestGraphStore, format: RdfFormat) -> AsyncHttpExecutor {
+  let url = test_http_server::serve(store.store().clone(), format);
+
+  AsyncHttpExecutor::new(format!("{url}/query"), format!("{url}/update"))
+}
+
+#[tokio::test]
+async fn test_async_construct() {
+  let expected = Struct {
+    field_0: "zero".to_owned(),
+    field_1: "one".to_owned(),
+  };
+
+  let mut store = TestGraphStore::new();
+  store.insert(&expected);
+
+  let triples = executor(&store)
+    .construct(Struct::sparql_algebra())
+    .await
+    .unwrap();
+
+  let dataset = collect_dataset(triples).await.unwrap();
+
+  let resource = Blank::new().next(&mut ()).into_term();
+
+  let actual = Struct::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(expected, actual);
+}
+
+#[tokio::test]
+async fn test_async_construct_turtle() {
+  let expected = Struct {
+    field_0: "zero".to_owned(),
+    field_1: "one".to_owned(),
+  };
+
+  let mut store = TestGraphStore::new();
+  store.insert(&expected);
+
+  let triples = executor_as(&store, RdfFormat::Turtle)
+    .construct(Struct::sparql_algebra())
+    .await
+    .unwrap();
+
+  let dataset = collect_dataset(triples).await.unwrap();
+
+  let resource = Blank::new().next(&mut ()).into_term();
+
+  let actual = Struct::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(expected, actual);
+}
+
+#[tokio::test]
+async fn test_async_select_ask_update() {
+  let store = TestGraphStore::new();
+  let executor = executor(&store);
+
+  let ask = Query::parse("ASK { <http://ex/s> <http://ex/p> ?o }", None).unwrap();
+
+  assert!(!executor.ask(ask.clone()).await.unwrap());
+
+  let update = Update::parse(
+    "INSERT DATA { <http://ex/s> <http://ex/p> \"a\", \"b\" }",
+    None,
+  )
+  .unwrap();
+  executor.update(update).await.unwrap();
+
+  assert!(executor.ask(ask).await.unwrap());
+
+  let select = Query::parse("SELECT ?o WHERE { <http://ex/s> <http://ex/p> ?o }", None).unwrap();
+  let solutions = executor
+    .select(select)
+    .await
+    .unwrap()
+    .try_collect::<Vec<_>>()
+    .await
+    .unwrap();
+
+  assert_eq!(solutions.len(), 2);
+}
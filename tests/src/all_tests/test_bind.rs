@@ -0,0 +1,64 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, Sparql, SparqlQuery};
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:given")]
+  given: String,
+
+  #[ld("ex:family")]
+  family: String,
+
+  #[ld("ex:displayName")]
+  #[sparql(bind = "CONCAT(?given, ' ', ?family)")]
+  display_name: String,
+
+  #[ld("ex:search")]
+  #[sparql(bind = "IRI(CONCAT(STR(<http://ex/search?q=>), ENCODE_FOR_URI(?family)))")]
+  search: IriBuf,
+
+  #[ld("ex:quoted")]
+  #[sparql(bind = "CONCAT(\"\"\"a \"?nobody\" b \"\"\", ?given)")]
+  quoted: String,
+}
+
+#[test]
+fn test_bind() {
+  let store = TestGraphStore::new();
+
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://ex/ada> <http://ex/given> "Ada" ;
+        <http://ex/family> "Lovelace" .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let dataset = store.query(Person::sparql_algebra());
+
+  let id = IriBuf::new("http://ex/ada".to_owned()).unwrap();
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(id.clone());
+
+  let actual = Person::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(
+    Person {
+      id,
+      given: "Ada".to_owned(),
+      family: "Lovelace".to_owned(),
+      display_name: "Ada Lovelace".to_owned(),
+      search: IriBuf::new("http://ex/search?q=Lovelace".to_owned()).unwrap(),
+      quoted: "a \"?nobody\" b Ada".to_owned(),
+    },
+    actual
+  );
+}
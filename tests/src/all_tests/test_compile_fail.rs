@@ -0,0 +1,4 @@
+#[test]
+fn test_compile_fail() {
+  trybuild::TestCases::new().compile_fail("ui/*.rs");
+}
@@ -1,61 +1,228 @@
+use std::collections::BTreeSet;
 use std::str::FromStr;
 
 use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
 use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
-use linked_data_sparql::{Sparql, SparqlQuery};
-use rdf_types::Generator;
+use linked_data_sparql::{
+  ConstructQuery, Executor, Sparql, SparqlQuery, ToConstructQuery, restore_datatypes,
+};
 use rdf_types::generator::Blank;
+use rdf_types::{Generator, RdfDisplay, Term};
+use spargebra::Update;
 
-#[test]
-fn test_datatypes() {
-  /// NOTE Commented out datatypes are not preserved by oxigraph
-  /// https://github.com/oxigraph/oxigraph/issues/526
-  #[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
-  #[ld(prefix("ex" = "http://ex/"))]
-  struct Datatypes {
-    // #[ld("ex:u8")]
-    // u8: u8,
-    // #[ld("ex:u16")]
-    // u16: u16,
-    // #[ld("ex:u32")]
-    // u32: u32,
-    #[ld("ex:u64")]
-    u64: u64,
-    // #[ld("ex:i8")]
-    // i8: i8,
-    // #[ld("ex:i16")]
-    // i16: i16,
-    // #[ld("ex:i32")]
-    // i32: i32,
-    // #[ld("ex:i64")]
-    // i64: i64,
-    #[ld("ex:String")]
-    string: String,
-    #[ld("ex:DateTime")]
-    date_time: xsd_types::DateTime,
-  }
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Datatypes {
+  #[ld("ex:u8")]
+  u8: u8,
+  #[ld("ex:u16")]
+  u16: u16,
+  #[ld("ex:u32")]
+  u32: u32,
+  #[ld("ex:u64")]
+  u64: u64,
+  #[ld("ex:i8")]
+  i8: i8,
+  #[ld("ex:i16")]
+  i16: i16,
+  #[ld("ex:i32")]
+  i32: i32,
+  #[ld("ex:i64")]
+  i64: i64,
+  #[ld("ex:String")]
+  string: String,
+  #[ld("ex:DateTime")]
+  date_time: xsd_types::DateTime,
+  #[ld("ex:IriBuf")]
+  iri: IriBuf,
+}
+
+/// The types `linked_data_next` does not deserialize, which are still
+/// matched by the generated query.
+#[allow(dead_code)]
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Literals {
+  #[ld("ex:bool")]
+  bool: bool,
+  #[ld("ex:u128")]
+  u128: u128,
+  #[ld("ex:usize")]
+  usize: usize,
+  #[ld("ex:i128")]
+  i128: i128,
+  #[ld("ex:isize")]
+  isize: isize,
+  #[ld("ex:f32")]
+  f32: f32,
+  #[ld("ex:f64")]
+  f64: f64,
+  #[ld("ex:char")]
+  char: char,
+  #[ld("ex:Date")]
+  date: xsd_types::Date,
+  #[ld("ex:Decimal")]
+  decimal: xsd_types::Decimal,
+  #[ld("ex:Duration")]
+  duration: xsd_types::Duration,
+  #[ld("ex:Time")]
+  time: xsd_types::Time,
+  #[ld("ex:Literal")]
+  literal: rdf_types::Literal,
+  #[ld("ex:Term")]
+  term: rdf_types::Term,
+}
 
-  let expected = Datatypes {
-    // u8: 255,
-    // u16: 65535,
-    // u32: 4294967295,
+fn expected() -> Datatypes {
+  Datatypes {
+    u8: 255,
+    u16: 65535,
+    u32: 4294967295,
     u64: 18446744073709551615,
-    // i8: -128,
-    // i16: -32768,
-    // i32: -2147483648,
-    // i64: -9223372036854775808,
+    i8: -128,
+    i16: -32768,
+    i32: -2147483648,
+    i64: -9223372036854775808,
     string: "test string".to_owned(),
     date_time: xsd_types::DateTime::from_str("2024-01-15T12:30:45Z").unwrap(),
-  };
+    iri: IriBuf::new("http://example.org/object".to_string()).unwrap(),
+  }
+}
 
-  let mut store = TestGraphStore::new();
-  store.insert(&expected);
+fn round_trip(store: &TestGraphStore, query: ConstructQuery, resource: Term) -> Datatypes {
+  let datatypes = query.template_datatypes();
+  let dataset = restore_datatypes(store.query(query.into()), &datatypes);
 
-  let dataset = store.query(Datatypes::sparql_algebra());
+  Datatypes::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap()
+}
+
+#[test]
+fn test_datatypes() {
+  let mut store = TestGraphStore::new();
+  store.insert(&expected());
 
   let resource = Blank::new().next(&mut ()).into_term();
 
-  let actual = Datatypes::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+  assert_eq!(
+    expected(),
+    round_trip(&store, Datatypes::to_query(), resource)
+  );
+}
+
+#[test]
+fn test_datatypes_coerced() {
+  let store = TestGraphStore::new();
+  let update = Update::parse(
+    r#"
+      PREFIX ex: <http://ex/>
+      PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+      INSERT DATA {
+        ex:datatypes ex:u8 "255" ; ex:u16 "65535" ; ex:u32 "4294967295"^^xsd:integer ;
+          ex:u64 "18446744073709551615"^^xsd:integer ; ex:i8 "-128"^^xsd:integer ;
+          ex:i16 "-32768"^^xsd:integer ; ex:i32 "-2147483648"^^xsd:integer ;
+          ex:i64 "-9223372036854775808"^^xsd:integer ; ex:String "test string" ;
+          ex:DateTime "2024-01-15T12:30:45Z"^^xsd:dateTime ;
+          ex:IriBuf <http://example.org/object> .
+      }
+    "#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let query = Datatypes::to_query().coerce_datatypes();
+
+  let resource = Term::iri(IriBuf::new("http://ex/datatypes".to_owned()).unwrap());
+
+  assert_eq!(expected(), round_trip(&store, query, resource));
+}
+
+#[test]
+fn test_datatypes_not_deserialized() {
+  let objects = [
+    r#""true"^^<http://www.w3.org/2001/XMLSchema#boolean>"#,
+    r#""340282366920938463463374607431768211455"^^<http://www.w3.org/2001/XMLSchema#integer>"#,
+    r#""42"^^<http://www.w3.org/2001/XMLSchema#integer>"#,
+    r#""-170141183460469231731687303715884105728"^^<http://www.w3.org/2001/XMLSchema#integer>"#,
+    r#""-42"^^<http://www.w3.org/2001/XMLSchema#integer>"#,
+    r#""1.5"^^<http://www.w3.org/2001/XMLSchema#float>"#,
+    r#""-2.25"^^<http://www.w3.org/2001/XMLSchema#double>"#,
+    r#""c""#,
+    r#""2024-01-15"^^<http://www.w3.org/2001/XMLSchema#date>"#,
+    r#""3.14"^^<http://www.w3.org/2001/XMLSchema#decimal>"#,
+    r#""P1DT2H"^^<http://www.w3.org/2001/XMLSchema#duration>"#,
+    r#""12:30:45"^^<http://www.w3.org/2001/XMLSchema#time>"#,
+    r#""chat"@fr"#,
+    r#"<http://example.org/term>"#,
+  ];
+  let predicates = [
+    "bool", "u128", "usize", "i128", "isize", "f32", "f64", "char", "Date", "Decimal", "Duration",
+    "Time", "Literal", "Term",
+  ];
+
+  let triples = predicates
+    .iter()
+    .zip(objects)
+    .map(|(predicate, object)| {
+      format!("<http://example.org/literals> <http://ex/{predicate}> {object} .")
+    })
+    .collect::<Vec<_>>();
+
+  let store = TestGraphStore::new();
+  let update = Update::parse(&format!("INSERT DATA {{ {} }}", triples.join("\n")), None).unwrap();
+  store.update(update).unwrap();
+
+  let actual = store
+    .query(Literals::sparql_algebra())
+    .iter()
+    .map(|quad| format!("{} .", quad.rdf_display()))
+    .collect::<BTreeSet<_>>();
+
+  assert_eq!(triples.into_iter().collect::<BTreeSet<_>>(), actual);
+}
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Small {
+  #[ld("ex:value")]
+  value: u8,
+}
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Shared {
+  #[ld("ex:value")]
+  value: i64,
+
+  #[ld("ex:small")]
+  small: Small,
+}
+
+#[test]
+fn test_datatypes_shared_predicate() {
+  let store = TestGraphStore::new();
+  let update = Update::parse(
+    r#"
+      PREFIX ex: <http://ex/>
+      INSERT DATA { ex:shared ex:value -1000 ; ex:small [ ex:value 7 ] . }
+    "#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let query = Shared::to_query().coerce_datatypes();
+  let datatypes = query.template_datatypes();
+  let dataset = restore_datatypes(store.query(query.into()), &datatypes);
+
+  let resource = Term::iri(IriBuf::new("http://ex/shared".to_owned()).unwrap());
 
-  assert_eq!(expected, actual);
+  assert_eq!(
+    Shared {
+      value: -1000,
+      small: Small { value: 7 },
+    },
+    Shared::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap()
+  );
 }
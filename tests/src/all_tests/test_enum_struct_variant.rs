@@ -0,0 +1,60 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject};
+use linked_data_sparql::{Executor, Sparql, ToConstructQuery, restore_datatypes};
+use spargebra::Update;
+
+#[test]
+fn test_enum_struct_variant() {
+  // Not `Serialize`: `linked_data_next` does not implement it for
+  // struct-like variants, so the data is inserted with an update.
+  #[derive(Sparql, Deserialize, Debug, PartialEq)]
+  #[ld(prefix("ex" = "http://ex/"))]
+  enum Shape {
+    #[ld("ex:circle")]
+    Circle {
+      #[ld("ex:radius")]
+      radius: i64,
+    },
+
+    #[ld("ex:rectangle")]
+    Rectangle {
+      #[ld("ex:width")]
+      width: i64,
+
+      #[ld("ex:height")]
+      height: i64,
+    },
+  }
+
+  let expected = Shape::Rectangle {
+    width: 3,
+    height: 4,
+  };
+
+  let store = TestGraphStore::new();
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://ex/shape> <http://ex/rectangle> _:rectangle .
+      _:rectangle <http://ex/width> 3 ; <http://ex/height> 4 .
+      <http://ex/other> <http://ex/circle> _:circle .
+      _:circle <http://ex/radius> 1 .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  // The store returns the `xsd:long` values as `xsd:integer`.
+  let query = Shape::to_query();
+  let datatypes = query.template_datatypes();
+  let dataset = restore_datatypes(store.query(query.into()), &datatypes);
+
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(
+    IriBuf::new("http://ex/shape".to_owned()).unwrap(),
+  );
+
+  let actual = Shape::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(expected, actual);
+}
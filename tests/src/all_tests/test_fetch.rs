@@ -0,0 +1,197 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, Serialize};
+use linked_data_sparql::reexport::sparesults::QuerySolution;
+use linked_data_sparql::{Error, Executor, Fetch, ROOT_PREDICATE, Sparql, SparqlQuery};
+use rdf_types::dataset::IndexedBTreeDataset;
+use spargebra::{Query, Update};
+use std::cell::Cell;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Struct {
+  #[ld("ex:field_0")]
+  field_0: String,
+
+  #[ld("ex:field_1")]
+  field_1: String,
+}
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct StructId {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:field")]
+  value: String,
+}
+
+#[test]
+fn test_fetch_one() {
+  let expected = Struct {
+    field_0: "zero".to_owned(),
+    field_1: "one".to_owned(),
+  };
+
+  let mut store = TestGraphStore::new();
+
+  assert_eq!(Struct::fetch_one(&store).unwrap(), None);
+
+  store.insert(&expected);
+
+  assert_eq!(Struct::fetch_one(&store).unwrap(), Some(expected));
+}
+
+/// Records the number of roots returned by the store.
+struct Recording<'a> {
+  store: &'a TestGraphStore,
+  roots: Cell<usize>,
+}
+
+impl Executor for Recording<'_> {
+  fn construct(&self, query: Query) -> Result<IndexedBTreeDataset, Error> {
+    let dataset = self.store.construct(query)?;
+    let roots = dataset
+      .iter()
+      .filter(|quad| matches!(quad.1, rdf_types::Term::Id(rdf_types::Id::Iri(iri)) if iri.as_str() == ROOT_PREDICATE))
+      .count();
+    self.roots.set(roots);
+
+    Ok(dataset)
+  }
+
+  fn select(&self, query: Query) -> Result<Vec<QuerySolution>, Error> {
+    self.store.select(query)
+  }
+
+  fn ask(&self, query: Query) -> Result<bool, Error> {
+    self.store.ask(query)
+  }
+
+  fn update(&self, update: Update) -> Result<(), Error> {
+    self.store.update(update)
+  }
+}
+
+#[test]
+fn test_fetch_one_limits_roots() {
+  let mut store = TestGraphStore::new();
+  for index in [3, 1, 2] {
+    store.insert(&StructId {
+      id: IriBuf::new(format!("http://example.org/myBar{index}")).unwrap(),
+      value: format!("value_{index}"),
+    });
+  }
+
+  let recording = Recording {
+    store: &store,
+    roots: Cell::new(0),
+  };
+
+  assert_eq!(
+    StructId::fetch_one(&recording).unwrap(),
+    Some(StructId {
+      id: IriBuf::new("http://example.org/myBar1".to_string()).unwrap(),
+      value: "value_1".to_owned(),
+    })
+  );
+  assert_eq!(recording.roots.get(), 1);
+
+  assert_eq!(StructId::fetch_all(&recording).unwrap().len(), 3);
+  assert_eq!(recording.roots.get(), 3);
+}
+
+#[test]
+fn test_fetch_all() {
+  let expected = vec![
+    StructId {
+      id: IriBuf::new("http://example.org/myBar1".to_string()).unwrap(),
+      value: "value_1".to_owned(),
+    },
+    StructId {
+      id: IriBuf::new("http://example.org/myBar2".to_string()).unwrap(),
+      value: "value_2".to_owned(),
+    },
+  ];
+
+  let mut store = TestGraphStore::new();
+  expected.iter().for_each(|resource| store.insert(resource));
+
+  assert_eq!(StructId::fetch_all(&store).unwrap(), expected);
+}
+
+/// A hand-written query, which marks its roots only when it overrides
+/// `sparql_algebra_with_roots`.
+#[derive(Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Manual {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:field")]
+  value: String,
+}
+
+impl SparqlQuery for Manual {
+  fn sparql_algebra() -> Query {
+    Query::parse(
+      "CONSTRUCT { ?s <http://ex/field> ?o } WHERE { ?s <http://ex/field> ?o }",
+      None,
+    )
+    .unwrap()
+  }
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct ManualWithRoots {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:field")]
+  value: String,
+}
+
+impl SparqlQuery for ManualWithRoots {
+  fn sparql_algebra() -> Query {
+    Manual::sparql_algebra()
+  }
+
+  fn sparql_algebra_with_roots() -> Query {
+    Query::parse(
+      &format!(
+        "CONSTRUCT {{ ?s <http://ex/field> ?o . ?s <{ROOT_PREDICATE}> true }} \
+         WHERE {{ ?s <http://ex/field> ?o }}"
+      ),
+      None,
+    )
+    .unwrap()
+  }
+}
+
+#[test]
+fn test_fetch_manual() {
+  let resource = StructId {
+    id: IriBuf::new("http://example.org/myBar".to_string()).unwrap(),
+    value: "value".to_owned(),
+  };
+
+  let mut store = TestGraphStore::new();
+
+  assert_eq!(Manual::fetch_all(&store).unwrap(), vec![]);
+
+  store.insert(&resource);
+
+  assert!(matches!(
+    Manual::fetch_all(&store),
+    Err(Error::UnexpectedResults)
+  ));
+  assert_eq!(
+    ManualWithRoots::fetch_one(&store).unwrap(),
+    Some(ManualWithRoots {
+      id: resource.id,
+      value: resource.value,
+    })
+  );
+}
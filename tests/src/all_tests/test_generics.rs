@@ -0,0 +1,201 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject};
+use linked_data_sparql::{Executor, Sparql, SparqlQuery, ToConstructQuery, restore_datatypes};
+use rdf_types::{Literal, LiteralType, Quad, Term};
+use spargebra::Update;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+#[derive(Sparql, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Envelope<T> {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:item")]
+  items: Vec<T>,
+}
+
+/// Not a linked data type.
+#[derive(Debug, PartialEq)]
+struct Unit;
+
+#[derive(Sparql, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Tagged<T> {
+  #[ld("ex:name")]
+  name: String,
+
+  #[ld(ignore)]
+  unit: PhantomData<T>,
+}
+
+// Not `Deserialize`: `linked_data_next` has no `Cow` values.
+#[allow(dead_code)]
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Named<'a, T>
+where
+  T: Clone,
+{
+  #[ld("ex:name")]
+  name: Cow<'a, str>,
+
+  #[ld("ex:value")]
+  value: T,
+}
+
+#[derive(Sparql, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+enum Either<L, R> {
+  #[ld("ex:left")]
+  Left(L),
+
+  #[ld("ex:right")]
+  Right(R),
+}
+
+// Not `Deserialize`: `linked_data_next` has no transparent structs.
+#[allow(dead_code)]
+#[derive(Sparql)]
+#[sparql(transparent)]
+struct Wrapper<T>(T);
+
+fn store() -> TestGraphStore {
+  let store = TestGraphStore::new();
+
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://ex/envelope> <http://ex/item> "a" , "b" .
+      <http://ex/named> <http://ex/name> "name" ; <http://ex/value> 1 .
+      <http://ex/choice> <http://ex/right> 2 .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  store
+}
+
+/// Runs the query of `T` and deserializes `iri`, with the datatypes of its
+/// integers restored.
+fn fetch<T>(iri: &str) -> T
+where
+  T: ToConstructQuery + LinkedDataDeserializeSubject,
+{
+  let query = T::to_query().coerce_datatypes();
+  let datatypes = query.template_datatypes();
+  let dataset = restore_datatypes(store().query(query.into()), &datatypes);
+
+  let resource = Term::iri(IriBuf::new(iri.to_owned()).unwrap());
+
+  T::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap()
+}
+
+/// The predicates of the template of `T`.
+fn template<T: ToConstructQuery>() -> Vec<String> {
+  let spargebra::Query::Construct { template, .. } = T::to_query().into() else {
+    unreachable!("a generated query is a CONSTRUCT query");
+  };
+
+  let mut predicates = template
+    .iter()
+    .map(|pattern| pattern.predicate.to_string())
+    .collect::<Vec<_>>();
+  predicates.sort();
+  predicates
+}
+
+#[test]
+fn test_generic_struct() {
+  let dataset = store().query(Envelope::<String>::sparql_algebra());
+
+  let id = IriBuf::new("http://ex/envelope".to_owned()).unwrap();
+  let resource = Term::iri(id.clone());
+
+  let mut actual =
+    Envelope::<String>::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+  actual.items.sort();
+
+  assert_eq!(
+    Envelope {
+      id,
+      items: vec!["a".to_owned(), "b".to_owned()],
+    },
+    actual
+  );
+}
+
+#[test]
+fn test_generic_ignored() {
+  assert_eq!(template::<Tagged<Unit>>(), ["<http://ex/name>"]);
+  assert_eq!(
+    Tagged {
+      name: "name".to_owned(),
+      unit: PhantomData::<Unit>,
+    },
+    fetch("http://ex/named")
+  );
+}
+
+#[test]
+fn test_generic_struct_with_lifetime() {
+  assert_eq!(
+    template::<Named<'static, i64>>(),
+    ["<http://ex/name>", "<http://ex/value>"]
+  );
+
+  let dataset = store().query(Named::<'static, i64>::sparql_algebra());
+  let named = [
+    ("http://ex/name", "name", xsd("string")),
+    ("http://ex/value", "1", xsd("integer")),
+  ]
+  .map(|(predicate, value, datatype)| quad("http://ex/named", predicate, value, datatype));
+
+  assert_eq!(dataset.len(), named.len());
+  assert!(named.iter().all(|quad| dataset.contains(quad.as_ref())));
+}
+
+#[test]
+fn test_generic_enum() {
+  assert_eq!(
+    template::<Either<String, i64>>(),
+    ["<http://ex/left>", "<http://ex/right>"]
+  );
+  assert_eq!(Either::<String, i64>::Right(2), fetch("http://ex/choice"));
+}
+
+#[test]
+fn test_generic_transparent() {
+  assert_eq!(
+    template::<Envelope<Wrapper<String>>>(),
+    template::<Envelope<String>>()
+  );
+
+  let dataset = store().query(Envelope::<Wrapper<String>>::sparql_algebra());
+  let items =
+    ["a", "b"].map(|item| quad("http://ex/envelope", "http://ex/item", item, xsd("string")));
+
+  assert_eq!(dataset.len(), items.len());
+  assert!(items.iter().all(|item| dataset.contains(item.as_ref())));
+}
+
+fn xsd(name: &str) -> String {
+  format!("http://www.w3.org/2001/XMLSchema#{name}")
+}
+
+fn quad(subject: &str, predicate: &str, value: &str, datatype: String) -> Quad {
+  let iri = |iri: &str| Term::iri(IriBuf::new(iri.to_owned()).unwrap());
+
+  Quad(
+    iri(subject),
+    iri(predicate),
+    Term::Literal(Literal::new(
+      value.to_owned(),
+      LiteralType::Any(IriBuf::new(datatype).unwrap()),
+    )),
+    None,
+  )
+}
@@ -0,0 +1,70 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, Sparql, SparqlQuery, ToConstructQuery};
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct StructId {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:name")]
+  name: String,
+
+  #[ld("ex:count")]
+  count: u64,
+
+  #[ld("ex:nickname")]
+  nickname: Option<String>,
+}
+
+fn insert_dirty() -> (TestGraphStore, StructId) {
+  let expected = StructId {
+    id: IriBuf::new("http://example.org/dirty".to_string()).unwrap(),
+    name: "name".to_owned(),
+    count: 18446744073709551615,
+    nickname: None,
+  };
+
+  let mut store = TestGraphStore::new();
+  store.insert(&expected);
+
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://example.org/dirty> <http://ex/name> <http://example.org/iri> , "nom"@fr .
+      <http://example.org/dirty> <http://ex/count> "foo"@en .
+      <http://example.org/dirty> <http://ex/nickname> <http://example.org/nickname> .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  (store, expected)
+}
+
+#[test]
+fn test_unguarded() {
+  let (store, expected) = insert_dirty();
+
+  let dataset = store.query(StructId::sparql_algebra());
+
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(expected.id);
+
+  assert!(StructId::deserialize_subject(&(), &(), &dataset, None, &resource).is_err());
+}
+
+#[test]
+fn test_guarded() {
+  let (store, expected) = insert_dirty();
+
+  let dataset = store.query(StructId::to_query().guard_leaves().into());
+
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(expected.id.clone());
+
+  let actual = StructId::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(expected, actual);
+}
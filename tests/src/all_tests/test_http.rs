@@ -0,0 +1,91 @@
+use crate::test_graph_store::TestGraphStore;
+use crate::test_http_server;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, HttpExecutor, Sparql, SparqlQuery};
+use oxigraph::io::RdfFormat;
+use rdf_types::Generator;
+use rdf_types::generator::Blank;
+use spargebra::{Query, Update};
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Struct {
+  #[ld("ex:field_0")]
+  field_0: String,
+
+  #[ld("ex:field_1")]
+  field_1: String,
+}
+
+fn executor(store: &TestGraphStore) -> HttpExecutor {
+  executor_as(store, RdfFormat::NTriples)
+}
+
+fn executor_as(store: &TestGraphStore, format: RdfFormat) -> HttpExecutor {
+  let url = test_http_server::serve(store.store().clone(), format);
+
+  HttpExecutor::new(format!("{url}/query"), format!("{url}/update"))
+}
+
+#[test]
+fn test_http_construct() {
+  let expected = Struct {
+    field_0: "zero".to_owned(),
+    field_1: "one".to_owned(),
+  };
+
+  let mut store = TestGraphStore::new();
+  store.insert(&expected);
+
+  let dataset = executor(&store)
+    .construct(Struct::sparql_algebra())
+    .unwrap();
+
+  let resource = Blank::new().next(&mut ()).into_term();
+
+  let actual = Struct::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_http_construct_turtle() {
+  let expected = Struct {
+    field_0: "zero".to_owned(),
+    field_1: "one".to_owned(),
+  };
+
+  let mut store = TestGraphStore::new();
+  store.insert(&expected);
+
+  let dataset = executor_as(&store, RdfFormat::Turtle)
+    .construct(Struct::sparql_algebra())
+    .unwrap();
+
+  let resource = Blank::new().next(&mut ()).into_term();
+
+  let actual = Struct::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_http_select_ask_update() {
+  let store = TestGraphStore::new();
+  let executor = executor(&store);
+
+  let ask = Query::parse("ASK { <http://ex/s> <http://ex/p> ?o }", None).unwrap();
+
+  assert!(!executor.ask(ask.clone()).unwrap());
+
+  let update = Update::parse("INSERT DATA { <http://ex/s> <http://ex/p> \"o\" }", None).unwrap();
+  executor.update(update).unwrap();
+
+  assert!(executor.ask(ask).unwrap());
+
+  let select = Query::parse("SELECT ?o WHERE { <http://ex/s> <http://ex/p> ?o }", None).unwrap();
+  let solutions = executor.select(select).unwrap();
+
+  assert_eq!(solutions.len(), 1);
+  assert_eq!(solutions[0].get("o").unwrap().to_string(), "\"o\"");
+}
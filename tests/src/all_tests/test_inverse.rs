@@ -0,0 +1,69 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, Sparql, SparqlQuery};
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Post {
+  #[ld("ex:title")]
+  title: String,
+}
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Author {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:name")]
+  name: String,
+
+  #[ld("ex:post")]
+  #[sparql(inverse = "ex:author")]
+  posts: Vec<Post>,
+}
+
+#[test]
+fn test_inverse() {
+  let store = TestGraphStore::new();
+
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://ex/ann> <http://ex/name> "Ann" .
+      <http://ex/first> <http://ex/author> <http://ex/ann> .
+      <http://ex/first> <http://ex/title> "First" .
+      <http://ex/second> <http://ex/author> <http://ex/ann> .
+      <http://ex/second> <http://ex/title> "Second" .
+      <http://ex/draft> <http://ex/title> "Draft" .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let dataset = store.query(Author::sparql_algebra());
+
+  let id = IriBuf::new("http://ex/ann".to_owned()).unwrap();
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(id.clone());
+
+  let mut actual = Author::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+  actual.posts.sort_by(|a, b| a.title.cmp(&b.title));
+
+  assert_eq!(
+    Author {
+      id,
+      name: "Ann".to_owned(),
+      posts: vec![
+        Post {
+          title: "First".to_owned(),
+        },
+        Post {
+          title: "Second".to_owned(),
+        },
+      ],
+    },
+    actual
+  );
+}
@@ -0,0 +1,165 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, LangMap, LangString, Sparql, SparqlQuery, ToConstructQuery};
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("rdfs" = "http://www.w3.org/2000/01/rdf-schema#"))]
+struct Labelled {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("rdfs:label")]
+  #[sparql(lang = "en")]
+  label: String,
+}
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("rdfs" = "http://www.w3.org/2000/01/rdf-schema#"))]
+struct Tagged {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("rdfs:label")]
+  label: LangString,
+}
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("rdfs" = "http://www.w3.org/2000/01/rdf-schema#"))]
+struct Translated {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("rdfs:label")]
+  labels: LangMap,
+}
+
+fn id() -> IriBuf {
+  IriBuf::new("http://example.org/thing".to_string()).unwrap()
+}
+
+fn insert_labels(labels: &str) -> TestGraphStore {
+  let store = TestGraphStore::new();
+
+  let update = Update::parse(
+    &format!(
+      "INSERT DATA {{ <http://example.org/thing> <http://www.w3.org/2000/01/rdf-schema#label> {labels} . }}"
+    ),
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  store
+}
+
+fn deserialize<T: LinkedDataDeserializeSubject>(
+  store: &TestGraphStore,
+  query: spargebra::Query,
+) -> T {
+  let dataset = store.query(query);
+
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(id());
+
+  T::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap()
+}
+
+#[test]
+fn test_lang_attribute() {
+  let store = insert_labels(r#""Hallo"@de , "Hello"@en-GB , "Hi""#);
+
+  let actual: Labelled = deserialize(&store, Labelled::sparql_algebra());
+
+  assert_eq!(
+    Labelled {
+      id: id(),
+      label: "Hello".to_owned(),
+    },
+    actual
+  );
+}
+
+#[test]
+fn test_lang_attribute_single_value() {
+  let store = insert_labels(r#""Hiya"@en , "Hello"@en , "Hello"@en-GB , "Hi""#);
+
+  let actual: Labelled = deserialize(&store, Labelled::sparql_algebra());
+
+  assert_eq!("Hello", actual.label);
+}
+
+#[test]
+fn test_lang_attribute_untagged_fallback() {
+  let store = insert_labels(r#""Hallo"@de , "Hi""#);
+
+  let actual: Labelled = deserialize(&store, Labelled::sparql_algebra());
+
+  assert_eq!("Hi", actual.label);
+}
+
+#[test]
+fn test_lang_attribute_any_fallback() {
+  let store = insert_labels(r#""Hallo"@de"#);
+
+  let actual: Labelled = deserialize(&store, Labelled::sparql_algebra());
+
+  assert_eq!("Hallo", actual.label);
+}
+
+#[test]
+fn test_prefer_languages() {
+  let store = insert_labels(r#""Hallo"@de , "Hello"@en , "Hi""#);
+
+  let actual: Tagged = deserialize(
+    &store,
+    Tagged::to_query().prefer_languages(&["fr", "de"]).into(),
+  );
+
+  assert_eq!(
+    LangString::new("Hallo".to_owned(), Some("de".parse().unwrap())),
+    actual.label
+  );
+}
+
+#[test]
+fn test_prefer_languages_untagged() {
+  let store = insert_labels(r#""Hello"@en , "Hi""#);
+
+  let actual: Tagged = deserialize(&store, Tagged::to_query().prefer_languages(&["fr"]).into());
+
+  assert_eq!(LangString::new("Hi".to_owned(), None), actual.label);
+}
+
+#[test]
+fn test_lang_map() {
+  let store = insert_labels(r#""Hallo"@de , "Hello"@en , "Hi""#);
+
+  let actual: Translated = deserialize(&store, Translated::sparql_algebra());
+
+  assert_eq!(
+    [("de", "Hallo"), ("en", "Hello")]
+      .into_iter()
+      .map(|(language, label)| (language.parse().unwrap(), label.to_owned()))
+      .collect::<LangMap>(),
+    actual.labels
+  );
+}
+
+#[test]
+fn test_lang_map_guarded() {
+  let store = insert_labels(r#""Hallo"@de , <http://example.org/label> , 42"#);
+
+  let dataset = store.query(Translated::sparql_algebra());
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(id());
+  assert!(Translated::deserialize_subject(&(), &(), &dataset, None, &resource).is_err());
+
+  let actual: Translated = deserialize(&store, Translated::to_query().guard_leaves().into());
+
+  assert_eq!(
+    [("de".parse().unwrap(), "Hallo".to_owned())]
+      .into_iter()
+      .collect::<LangMap>(),
+    actual.labels
+  );
+}
@@ -0,0 +1,68 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, List, Sparql, SparqlQuery};
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Recipe {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:name")]
+  name: String,
+
+  #[ld("ex:steps")]
+  steps: List<String>,
+}
+
+fn recipe(id: &str, name: &str, steps: &[&str]) -> Recipe {
+  Recipe {
+    id: IriBuf::new(id.to_owned()).unwrap(),
+    name: name.to_owned(),
+    steps: steps.iter().map(|step| step.to_string()).collect(),
+  }
+}
+
+fn deserialize(store: &TestGraphStore, expected: &Recipe) -> Recipe {
+  let dataset = store.query(Recipe::sparql_algebra());
+
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(expected.id.clone());
+
+  Recipe::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap()
+}
+
+#[test]
+fn test_list() {
+  let store = TestGraphStore::new();
+
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://ex/tea> <http://ex/name> "Tea" .
+      <http://ex/tea> <http://ex/steps> ( "Boil" "Steep" "Pour" ) .
+      <http://ex/water> <http://ex/name> "Water" .
+      <http://ex/water> <http://ex/steps> () .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  for expected in [
+    recipe("http://ex/tea", "Tea", &["Boil", "Steep", "Pour"]),
+    recipe("http://ex/water", "Water", &[]),
+  ] {
+    assert_eq!(expected, deserialize(&store, &expected));
+  }
+}
+
+#[test]
+fn test_list_round_trip() {
+  let expected = recipe("http://ex/toast", "Toast", &["Slice", "Toast", "Butter"]);
+
+  let mut store = TestGraphStore::new();
+  store.insert(&expected);
+
+  assert_eq!(expected, deserialize(&store, &expected));
+}
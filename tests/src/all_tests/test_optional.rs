@@ -0,0 +1,71 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, Sparql, SparqlQuery};
+use rdf_types::dataset::IndexedBTreeDataset;
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:name")]
+  name: String,
+
+  #[ld("ex:nickname")]
+  nickname: Option<String>,
+
+  #[ld("ex:email")]
+  emails: Vec<String>,
+}
+
+fn iri(name: &str) -> IriBuf {
+  IriBuf::new(format!("http://example.org/{name}")).unwrap()
+}
+
+fn deserialize(dataset: &IndexedBTreeDataset, id: IriBuf) -> Person {
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(id);
+  Person::deserialize_subject(&(), &(), dataset, None, &resource).unwrap()
+}
+
+#[test]
+fn test_optional() {
+  let store = TestGraphStore::new();
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://example.org/ann> <http://ex/name> "Ann" .
+      <http://example.org/bob> <http://ex/name> "Bob" ;
+        <http://ex/nickname> "Bobby" ;
+        <http://ex/email> "bob@example.org" , "bobby@example.org" .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let dataset = store.query(Person::sparql_algebra());
+
+  assert_eq!(
+    Person {
+      id: iri("ann"),
+      name: "Ann".to_owned(),
+      nickname: None,
+      emails: vec![],
+    },
+    deserialize(&dataset, iri("ann"))
+  );
+
+  let mut bob = deserialize(&dataset, iri("bob"));
+  bob.emails.sort();
+  assert_eq!(
+    Person {
+      id: iri("bob"),
+      name: "Bob".to_owned(),
+      nickname: Some("Bobby".to_owned()),
+      emails: vec!["bob@example.org".to_owned(), "bobby@example.org".to_owned()],
+    },
+    bob
+  );
+}
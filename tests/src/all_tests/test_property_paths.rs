@@ -0,0 +1,104 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, Sparql, SparqlQuery};
+use rdf_types::Generator;
+use rdf_types::generator::Blank;
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:city")]
+  #[sparql(path = "ex:address/ex:city")]
+  city: String,
+
+  #[ld("ex:nick")]
+  #[sparql(path = "ex:nick|ex:alias")]
+  nicknames: Vec<String>,
+
+  #[ld("ex:group")]
+  #[sparql(path = "^ex:member")]
+  groups: Vec<IriBuf>,
+
+  #[ld("ex:reaches")]
+  #[sparql(path = "ex:knows+")]
+  reachable: Vec<IriBuf>,
+}
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+#[sparql(property_paths)]
+enum Contact {
+  #[ld("ex:email")]
+  Email(#[ld("ex:value")] String),
+
+  #[ld("ex:phone")]
+  Phone(#[ld("ex:value")] String),
+}
+
+fn iri(value: &str) -> IriBuf {
+  IriBuf::new(value.to_owned()).unwrap()
+}
+
+#[test]
+fn test_property_paths() {
+  let store = TestGraphStore::new();
+
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://ex/p> <http://ex/address> _:a .
+      _:a <http://ex/city> "Paris" .
+      <http://ex/p> <http://ex/nick> "Pat" .
+      <http://ex/p> <http://ex/alias> "P" .
+      <http://ex/g> <http://ex/member> <http://ex/p> .
+      <http://ex/p> <http://ex/knows> <http://ex/q> .
+      <http://ex/q> <http://ex/knows> <http://ex/r> .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let dataset = store.query(Person::sparql_algebra());
+
+  let id = iri("http://ex/p");
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(id.clone());
+
+  let mut actual = Person::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+  actual.nicknames.sort();
+  actual.reachable.sort();
+
+  // Every match is written under the field predicate, and nothing under the
+  // predicates of the paths.
+  assert_eq!(
+    Person {
+      id,
+      city: "Paris".to_owned(),
+      nicknames: vec!["P".to_owned(), "Pat".to_owned()],
+      groups: vec![iri("http://ex/g")],
+      reachable: vec![iri("http://ex/q"), iri("http://ex/r")],
+    },
+    actual
+  );
+  assert_eq!(dataset.len(), 6);
+}
+
+#[test]
+fn test_enum_property_paths() {
+  let expected = Contact::Phone("555-0100".to_owned());
+
+  let mut store = TestGraphStore::new();
+  store.insert(&expected);
+
+  let dataset = store.query(Contact::sparql_algebra());
+
+  let resource = Blank::new().next(&mut ()).into_term();
+
+  let actual = Contact::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(expected, actual);
+}
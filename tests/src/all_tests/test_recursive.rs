@@ -0,0 +1,259 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{
+  Context, Deserialize, FromLinkedDataError, LinkedDataDeserializePredicateObjects,
+  LinkedDataDeserializeSubject,
+};
+use linked_data_sparql::{Executor, Ref, Sparql, SparqlQuery};
+use rdf_types::Term;
+use rdf_types::dataset::PatternMatchingDataset;
+use spargebra::Update;
+
+// Not `Serialize`, and `Deserialize` is written by hand: the bounds
+// `linked_data_next` derives for a recursive field never resolve, so the data
+// is inserted with an update.
+#[derive(Sparql, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Category {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:name")]
+  name: String,
+
+  #[ld("ex:parent")]
+  parent: Option<Box<Self>>,
+}
+
+impl LinkedDataDeserializeSubject for Category {
+  fn deserialize_subject_in<D>(
+    vocabulary: &(),
+    interpretation: &(),
+    dataset: &D,
+    graph: Option<&Term>,
+    resource: &Term,
+    context: Context<()>,
+  ) -> Result<Self, FromLinkedDataError>
+  where
+    D: PatternMatchingDataset<Resource = Term>,
+  {
+    Ok(Self {
+      id: IriBuf::deserialize_subject_in(
+        vocabulary,
+        interpretation,
+        dataset,
+        graph,
+        resource,
+        context,
+      )?,
+      name: String::deserialize_objects_in(
+        vocabulary,
+        interpretation,
+        dataset,
+        graph,
+        &objects(dataset, graph, resource, "http://ex/name"),
+        context,
+      )?,
+      parent: Option::deserialize_objects_in(
+        vocabulary,
+        interpretation,
+        dataset,
+        graph,
+        &objects(dataset, graph, resource, "http://ex/parent"),
+        context,
+      )?,
+    })
+  }
+}
+
+fn objects<D>(dataset: &D, graph: Option<&Term>, resource: &Term, predicate: &str) -> Vec<Term>
+where
+  D: PatternMatchingDataset<Resource = Term>,
+{
+  let predicate = Term::iri(iri(predicate));
+
+  dataset
+    .quad_objects(graph, resource, &predicate)
+    .cloned()
+    .collect()
+}
+
+fn category(name: &str, parent: Option<Category>) -> Category {
+  Category {
+    id: iri(&format!("http://example.org/{name}")),
+    name: name.to_owned(),
+    parent: parent.map(Box::new),
+  }
+}
+
+#[test]
+fn test_recursive() {
+  let expected = category(
+    "leaf",
+    Some(category("branch", Some(category("root", None)))),
+  );
+
+  let store = TestGraphStore::new();
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://example.org/leaf> <http://ex/name> "leaf" ;
+        <http://ex/parent> <http://example.org/branch> .
+      <http://example.org/branch> <http://ex/name> "branch" ;
+        <http://ex/parent> <http://example.org/root> .
+      <http://example.org/root> <http://ex/name> "root" .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let dataset = store.query(Category::sparql_algebra());
+
+  let resource = Term::iri(expected.id.clone());
+
+  let actual = Category::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(expected, actual);
+}
+
+// Mutually recursive, through an alias: each pattern links to the other
+// type, and the resources reachable through both links are expanded.
+#[derive(Sparql, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Team {
+  #[ld("ex:name")]
+  name: String,
+
+  #[ld("ex:lead")]
+  lead: Option<Member>,
+}
+
+type Squad = Team;
+
+#[derive(Sparql, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Member {
+  #[ld("ex:name")]
+  name: String,
+
+  #[ld("ex:team")]
+  teams: Vec<Squad>,
+}
+
+impl LinkedDataDeserializeSubject for Team {
+  fn deserialize_subject_in<D>(
+    vocabulary: &(),
+    interpretation: &(),
+    dataset: &D,
+    graph: Option<&Term>,
+    resource: &Term,
+    context: Context<()>,
+  ) -> Result<Self, FromLinkedDataError>
+  where
+    D: PatternMatchingDataset<Resource = Term>,
+  {
+    Ok(Self {
+      name: String::deserialize_objects_in(
+        vocabulary,
+        interpretation,
+        dataset,
+        graph,
+        &objects(dataset, graph, resource, "http://ex/name"),
+        context,
+      )?,
+      lead: Option::deserialize_objects_in(
+        vocabulary,
+        interpretation,
+        dataset,
+        graph,
+        &objects(dataset, graph, resource, "http://ex/lead"),
+        context,
+      )?,
+    })
+  }
+}
+
+#[derive(Sparql, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Node {
+  #[ld("ex:name")]
+  name: String,
+
+  #[ld("ex:next")]
+  next: Option<Ref<Node>>,
+}
+
+fn iri(value: &str) -> IriBuf {
+  IriBuf::new(value.to_owned()).unwrap()
+}
+
+#[test]
+fn test_mutual_recursion() {
+  let store = TestGraphStore::new();
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://example.org/core> <http://ex/name> "core" ;
+        <http://ex/lead> <http://example.org/ann> .
+      <http://example.org/ann> <http://ex/name> "Ann" ;
+        <http://ex/team> <http://example.org/platform> .
+      <http://example.org/platform> <http://ex/name> "platform" ;
+        <http://ex/lead> <http://example.org/bob> .
+      <http://example.org/bob> <http://ex/name> "Bob" .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let dataset = store.query(Team::sparql_algebra());
+
+  let resource = Term::iri(iri("http://example.org/core"));
+
+  let actual = Team::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(
+    Team {
+      name: "core".to_owned(),
+      lead: Some(Member {
+        name: "Ann".to_owned(),
+        teams: vec![Team {
+          name: "platform".to_owned(),
+          lead: Some(Member {
+            name: "Bob".to_owned(),
+            teams: Vec::new(),
+          }),
+        }],
+      }),
+    },
+    actual
+  );
+}
+
+#[test]
+fn test_ref_not_expanded() {
+  let store = TestGraphStore::new();
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://example.org/a> <http://ex/name> "a" ;
+        <http://ex/next> <http://example.org/b> .
+      <http://example.org/b> <http://ex/name> "b" .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let dataset = store.query(Node::sparql_algebra());
+
+  let resource = Term::iri(iri("http://example.org/a"));
+
+  let actual = Node::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(
+    Node {
+      name: "a".to_owned(),
+      next: Some(Ref::new(iri("http://example.org/b"))),
+    },
+    actual
+  );
+}
@@ -0,0 +1,85 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, Ref, Sparql, SparqlQuery};
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:name")]
+  name: String,
+}
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Book {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:author")]
+  author: Ref<Person>,
+}
+
+fn iri(name: &str) -> IriBuf {
+  IriBuf::new(format!("http://example.org/{name}")).unwrap()
+}
+
+fn insert() -> (TestGraphStore, Person, Book) {
+  let person = Person {
+    id: iri("person"),
+    name: "name".to_owned(),
+  };
+  let book = Book {
+    id: iri("book"),
+    author: Ref::new(person.id.clone()),
+  };
+
+  let mut store = TestGraphStore::new();
+  store.insert(&person);
+  store.insert(&book);
+
+  (store, person, book)
+}
+
+#[test]
+fn test_ref() {
+  let (store, person, expected) = insert();
+
+  let dataset = store.query(Book::sparql_algebra());
+
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(expected.id.clone());
+
+  let actual = Book::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(expected, actual);
+  assert_eq!(Some(person), actual.author.load(&store).unwrap());
+}
+
+#[test]
+fn test_ref_not_hydrated() {
+  let (store, _, _) = insert();
+
+  let dataset = store.query(Book::sparql_algebra());
+
+  assert_eq!(1, dataset.len());
+}
+
+#[test]
+fn test_ref_requires_iri() {
+  let (store, _, _) = insert();
+
+  let update = Update::parse(
+    r#"INSERT DATA { <http://example.org/other> <http://ex/author> "literal" . }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let dataset = store.query(Book::sparql_algebra());
+
+  assert_eq!(1, dataset.len());
+}
@@ -0,0 +1,252 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, Serialize};
+use linked_data_sparql::{Page, Repository, Sparql};
+
+#[derive(Sparql, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[ld(type = "http://ex/Type")]
+#[ld(prefix("ex" = "http://ex/"))]
+struct StructId {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:field")]
+  value: String,
+}
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Struct {
+  #[ld("ex:field_0")]
+  field_0: String,
+
+  #[ld("ex:field_1")]
+  field_1: String,
+}
+
+fn struct_id(index: usize) -> StructId {
+  StructId {
+    id: IriBuf::new(format!("http://example.org/resource{index}")).unwrap(),
+    value: format!("value_{index}"),
+  }
+}
+
+#[test]
+fn test_repository_save_get() {
+  let store = TestGraphStore::new();
+  let repository = Repository::<StructId, _>::new(&store);
+
+  let expected = struct_id(0);
+
+  assert_eq!(repository.get(&expected.id).unwrap(), None);
+  assert!(!repository.exists(&expected.id).unwrap());
+
+  repository.save(&expected).unwrap();
+
+  assert_eq!(
+    repository.get(&expected.id).unwrap(),
+    Some(expected.clone())
+  );
+  assert!(repository.exists(&expected.id).unwrap());
+  assert_eq!(repository.get(&struct_id(1).id).unwrap(), None);
+}
+
+#[test]
+fn test_repository_save_replaces() {
+  let store = TestGraphStore::new();
+  let repository = Repository::<StructId, _>::new(&store);
+
+  let mut expected = struct_id(0);
+  repository.save(&expected).unwrap();
+
+  expected.value = "updated".to_owned();
+  repository.save(&expected).unwrap();
+
+  assert_eq!(repository.count().unwrap(), 1);
+  assert_eq!(repository.get(&expected.id).unwrap(), Some(expected));
+}
+
+#[test]
+fn test_repository_list_count() {
+  let store = TestGraphStore::new();
+  let repository = Repository::<StructId, _>::new(&store);
+
+  let expected = (0..5).map(struct_id).collect::<Vec<_>>();
+  for resource in &expected {
+    repository.save(resource).unwrap();
+  }
+
+  assert_eq!(repository.count().unwrap(), 5);
+
+  let first = repository
+    .list(Page {
+      offset: 0,
+      limit: 3,
+    })
+    .unwrap();
+  let second = repository
+    .list(Page {
+      offset: 3,
+      limit: 3,
+    })
+    .unwrap();
+
+  assert_eq!(first, expected[..3]);
+  assert_eq!(second, expected[3..]);
+}
+
+#[test]
+fn test_repository_delete() {
+  let store = TestGraphStore::new();
+  let repository = Repository::<StructId, _>::new(&store);
+
+  let deleted = struct_id(0);
+  let kept = struct_id(1);
+  repository.save(&deleted).unwrap();
+  repository.save(&kept).unwrap();
+
+  repository.delete(&deleted.id).unwrap();
+
+  assert!(!repository.exists(&deleted.id).unwrap());
+  assert_eq!(repository.get(&kept.id).unwrap(), Some(kept));
+  assert_eq!(repository.count().unwrap(), 1);
+}
+
+#[test]
+fn test_repository_blank_node() {
+  let store = TestGraphStore::new();
+  let repository = Repository::<Struct, _>::new(&store);
+
+  let expected = || Struct {
+    field_0: "zero".to_owned(),
+    field_1: "one".to_owned(),
+  };
+
+  repository.save(&expected()).unwrap();
+  repository.save(&expected()).unwrap();
+
+  let actual = repository
+    .list(Page {
+      offset: 0,
+      limit: 10,
+    })
+    .unwrap();
+
+  assert_eq!(repository.count().unwrap(), 2);
+  assert_eq!(actual, vec![expected(), expected()]);
+}
+
+#[derive(Sparql, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Address {
+  #[ld("ex:street")]
+  street: String,
+
+  #[ld("ex:city")]
+  city: String,
+}
+
+#[derive(Sparql, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+enum Contact {
+  #[ld("ex:email")]
+  Email(String),
+
+  #[ld("ex:postal")]
+  Postal(Address),
+}
+
+#[derive(Sparql, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:address")]
+  address: Address,
+
+  #[ld("ex:contact")]
+  contact: Contact,
+
+  #[ld("ex:friend")]
+  friend: Option<StructId>,
+}
+
+fn triples(store: &TestGraphStore) -> usize {
+  store.store().len().unwrap()
+}
+
+#[test]
+fn test_repository_nested() {
+  let store = TestGraphStore::new();
+  let repository = Repository::<Person, _>::new(&store);
+
+  let friend = struct_id(1);
+  Repository::<StructId, _>::new(&store)
+    .save(&friend)
+    .unwrap();
+  let kept = triples(&store);
+
+  let mut expected = Person {
+    id: IriBuf::new("http://example.org/alice".to_owned()).unwrap(),
+    address: Address {
+      street: "1 Main Street".to_owned(),
+      city: "Springfield".to_owned(),
+    },
+    contact: Contact::Postal(Address {
+      street: "2 Side Street".to_owned(),
+      city: "Shelbyville".to_owned(),
+    }),
+    friend: Some(friend.clone()),
+  };
+
+  repository.save(&expected).unwrap();
+  let saved = triples(&store);
+
+  expected.address.city = "Capital City".to_owned();
+  expected.contact = Contact::Email("alice@example.org".to_owned());
+  repository.save(&expected).unwrap();
+
+  assert_eq!(repository.count().unwrap(), 1);
+  assert_eq!(
+    repository.get(&expected.id).unwrap(),
+    Some(expected.clone())
+  );
+  assert!(triples(&store) < saved);
+
+  repository.delete(&expected.id).unwrap();
+
+  assert_eq!(repository.get(&expected.id).unwrap(), None);
+  assert_eq!(triples(&store), kept);
+  assert_eq!(
+    Repository::<StructId, _>::new(&store)
+      .get(&friend.id)
+      .unwrap(),
+    Some(friend)
+  );
+}
+
+#[test]
+fn test_repository_list_skips_blank_nodes() {
+  let store = TestGraphStore::new();
+  let repository = Repository::<StructId, _>::new(&store);
+
+  store
+    .store()
+    .update("INSERT DATA { _:blank a <http://ex/Type> ; <http://ex/field> \"blank\" }")
+    .unwrap();
+
+  let expected = struct_id(0);
+  repository.save(&expected).unwrap();
+
+  assert_eq!(repository.count().unwrap(), 1);
+  assert_eq!(
+    repository
+      .list(Page {
+        offset: 0,
+        limit: 1,
+      })
+      .unwrap(),
+    vec![expected]
+  );
+}
@@ -0,0 +1,62 @@
+use crate::test_graph_store::TestGraphStore;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Fetch, Sparql, SparqlQuery, extract_roots};
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Struct {
+  #[ld("ex:field_0")]
+  field_0: String,
+
+  #[ld("ex:field_1")]
+  field_1: String,
+}
+
+#[derive(Serialize)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Container {
+  #[ld("ex:item")]
+  items: Vec<Struct>,
+}
+
+fn container() -> Container {
+  Container {
+    items: vec![
+      Struct {
+        field_0: "a".to_owned(),
+        field_1: "b".to_owned(),
+      },
+      Struct {
+        field_0: "c".to_owned(),
+        field_1: "d".to_owned(),
+      },
+    ],
+  }
+}
+
+#[test]
+fn test_extract_roots() {
+  let mut store = TestGraphStore::new();
+  store.insert(&container());
+
+  let (roots, dataset) = extract_roots(store.query(Struct::sparql_algebra_with_roots())).unwrap();
+
+  assert_eq!(roots.len(), 2);
+
+  for root in &roots {
+    Struct::deserialize_subject(&(), &(), &dataset, None, root).unwrap();
+  }
+}
+
+#[test]
+fn test_fetch_blank_node_roots() {
+  let expected = container();
+
+  let mut store = TestGraphStore::new();
+  store.insert(&expected);
+
+  let mut actual = Struct::fetch_all(&store).unwrap();
+  actual.sort_by(|left, right| left.field_0.cmp(&right.field_0));
+
+  assert_eq!(expected.items, actual);
+}
@@ -0,0 +1,76 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, Seq, Sparql, SparqlQuery};
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Paper {
+  #[ld(id)]
+  id: IriBuf,
+
+  #[ld("ex:title")]
+  title: String,
+
+  #[ld("ex:authors")]
+  authors: Seq<String>,
+}
+
+fn deserialize(store: &TestGraphStore, id: &IriBuf) -> Paper {
+  let dataset = store.query(Paper::sparql_algebra());
+
+  let resource = <rdf_types::Term as rdf_types::FromIri>::from_iri(id.clone());
+
+  Paper::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap()
+}
+
+#[test]
+fn test_seq() {
+  let store = TestGraphStore::new();
+
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://ex/paper> <http://ex/title> "Paper" .
+      <http://ex/paper> <http://ex/authors> _:authors .
+      _:authors a <http://www.w3.org/1999/02/22-rdf-syntax-ns#Seq> .
+      _:authors <http://www.w3.org/1999/02/22-rdf-syntax-ns#_1> "Grace" .
+      _:authors <http://www.w3.org/1999/02/22-rdf-syntax-ns#_2> "Ada" .
+      _:authors <http://www.w3.org/1999/02/22-rdf-syntax-ns#_10> "Barbara" .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let id = IriBuf::new("http://ex/paper".to_owned()).unwrap();
+
+  assert_eq!(
+    Paper {
+      id: id.clone(),
+      title: "Paper".to_owned(),
+      authors: ["Grace", "Ada", "Barbara"]
+        .into_iter()
+        .map(str::to_owned)
+        .collect(),
+    },
+    deserialize(&store, &id)
+  );
+}
+
+#[test]
+fn test_seq_round_trip() {
+  let expected = Paper {
+    id: IriBuf::new("http://ex/other".to_owned()).unwrap(),
+    title: "Other".to_owned(),
+    authors: ["Linus", "Ken", "Dennis"]
+      .into_iter()
+      .map(str::to_owned)
+      .collect(),
+  };
+
+  let mut store = TestGraphStore::new();
+  store.insert(&expected);
+
+  assert_eq!(expected, deserialize(&store, &expected.id));
+}
@@ -0,0 +1,76 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject};
+use linked_data_sparql::{ConstructQuery, Executor, Sparql, SparqlQuery, ToConstructQuery};
+use rdf_types::BlankIdBuf;
+use spargebra::Update;
+use spargebra::algebra::{Expression, Function};
+use spargebra::term::{Literal, Variable};
+
+fn long_name(binding_variable: Variable) -> ConstructQuery {
+  let constraint = Expression::Greater(
+    Box::new(Expression::FunctionCall(
+      Function::StrLen,
+      vec![Expression::Variable(binding_variable.clone())],
+    )),
+    Box::new(Expression::Literal(Literal::from(3))),
+  );
+
+  String::to_query_with_binding(binding_variable.clone())
+    .with_constraint(binding_variable, constraint)
+}
+
+/// `BlankIdBuf` comes from `rdf_types`, so it cannot implement
+/// `ToConstructQuery` here.
+fn blank_node(binding_variable: Variable) -> ConstructQuery {
+  let constraint = Expression::FunctionCall(
+    Function::IsBlank,
+    vec![Expression::Variable(binding_variable.clone())],
+  );
+
+  ConstructQuery::default().with_constraint(binding_variable, constraint)
+}
+
+#[derive(Sparql, Deserialize, Debug)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Named {
+  #[ld("ex:name")]
+  #[sparql(sparql_with = "long_name")]
+  name: String,
+
+  #[ld("ex:node")]
+  #[sparql(sparql_with = "blank_node")]
+  node: BlankIdBuf,
+}
+
+fn resource(name: &str) -> rdf_types::Term {
+  <rdf_types::Term as rdf_types::FromIri>::from_iri(
+    IriBuf::new(format!("http://ex/{name}")).unwrap(),
+  )
+}
+
+#[test]
+fn test_sparql_with() {
+  let store = TestGraphStore::new();
+
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://ex/alice> <http://ex/name> "Alice" ; <http://ex/node> _:alice .
+      <http://ex/al> <http://ex/name> "Al" ; <http://ex/node> _:al .
+      <http://ex/carol> <http://ex/name> "Carol" ; <http://ex/node> <http://ex/carol-node> .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  let dataset = store.query(Named::sparql_algebra());
+
+  let alice = Named::deserialize_subject(&(), &(), &dataset, None, &resource("alice")).unwrap();
+  assert_eq!(alice.name, "Alice");
+  assert!(alice.node.as_str().starts_with("_:"));
+
+  // The name is too short, and the node is not a blank node.
+  assert!(Named::deserialize_subject(&(), &(), &dataset, None, &resource("al")).is_err());
+  assert!(Named::deserialize_subject(&(), &(), &dataset, None, &resource("carol")).is_err());
+}
@@ -0,0 +1,28 @@
+use crate::test_graph_store::TestGraphStore;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Sparql, ToConstructQuery, restore_datatypes};
+use rdf_types::Generator;
+use rdf_types::generator::Blank;
+
+#[test]
+fn test_struct_tuple() {
+  #[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+  #[ld(prefix("ex" = "http://ex/"))]
+  struct Point(#[ld("ex:x")] i64, #[ld("ex:y")] i64);
+
+  let expected = Point(1, 2);
+
+  let mut store = TestGraphStore::new();
+  store.insert(&expected);
+
+  // The store returns the `xsd:long` values as `xsd:integer`.
+  let query = Point::to_query();
+  let datatypes = query.template_datatypes();
+  let dataset = restore_datatypes(store.query(query.into()), &datatypes);
+
+  let resource = Blank::new().next(&mut ()).into_term();
+
+  let actual = Point::deserialize_subject(&(), &(), &dataset, None, &resource).unwrap();
+
+  assert_eq!(expected, actual);
+}
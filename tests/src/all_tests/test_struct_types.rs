@@ -0,0 +1,94 @@
+use crate::test_graph_store::TestGraphStore;
+use iref::IriBuf;
+use linked_data_next::{Deserialize, LinkedDataDeserializeSubject, Serialize};
+use linked_data_sparql::{Executor, Sparql, SparqlQuery};
+use rdf_types::dataset::IndexedBTreeDataset;
+use spargebra::Update;
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+#[sparql(type = ["ex:Person", "ex:Employee"])]
+struct Employee {
+  #[ld("ex:name")]
+  name: String,
+}
+
+#[derive(Sparql, Serialize, Deserialize, Debug, PartialEq)]
+#[ld(prefix("ex" = "http://ex/"))]
+#[ld(type = "ex:Animal")]
+#[sparql(type_match = "subclass")]
+struct Animal {
+  #[ld("ex:name")]
+  name: String,
+}
+
+fn store() -> TestGraphStore {
+  let store = TestGraphStore::new();
+
+  let update = Update::parse(
+    r#"INSERT DATA {
+      <http://ex/ann> a <http://ex/Person>, <http://ex/Employee> ;
+        <http://ex/name> "Ann" .
+      <http://ex/bob> a <http://ex/Person> ;
+        <http://ex/name> "Bob" .
+      <http://ex/Dog> <http://www.w3.org/2000/01/rdf-schema#subClassOf> <http://ex/Mammal> .
+      <http://ex/Mammal> <http://www.w3.org/2000/01/rdf-schema#subClassOf> <http://ex/Animal> .
+      <http://ex/rex> a <http://ex/Dog> ;
+        <http://ex/name> "Rex" .
+      <http://ex/rock> a <http://ex/Rock> ;
+        <http://ex/name> "Rock" .
+    }"#,
+    None,
+  )
+  .unwrap();
+  store.update(update).unwrap();
+
+  store
+}
+
+fn term(iri: &str) -> rdf_types::Term {
+  <rdf_types::Term as rdf_types::FromIri>::from_iri(IriBuf::new(iri.to_owned()).unwrap())
+}
+
+fn deserialize<T: LinkedDataDeserializeSubject>(
+  dataset: &IndexedBTreeDataset,
+  id: &str,
+) -> Option<T> {
+  T::deserialize_subject(&(), &(), dataset, None, &term(id)).ok()
+}
+
+#[test]
+fn test_struct_types() {
+  let dataset = store().query(Employee::sparql_algebra());
+
+  // Only Ann has both types.
+  assert_eq!(
+    Some(Employee {
+      name: "Ann".to_owned(),
+    }),
+    deserialize(&dataset, "http://ex/ann")
+  );
+  assert_eq!(None, deserialize::<Employee>(&dataset, "http://ex/bob"));
+}
+
+#[test]
+fn test_struct_type_subclass() {
+  let dataset = store().query(Animal::sparql_algebra());
+
+  // Rex, an instance of a subclass, with the declared type instead of
+  // `ex:Dog`.
+  assert_eq!(
+    Some(Animal {
+      name: "Rex".to_owned(),
+    }),
+    deserialize(&dataset, "http://ex/rex")
+  );
+  assert_eq!(None, deserialize::<Animal>(&dataset, "http://ex/rock"));
+
+  assert!(
+    dataset
+      .iter()
+      .any(|quad| *quad.2 == term("http://ex/Animal"))
+  );
+  assert!(!dataset.iter().any(|quad| *quad.2 == term("http://ex/Dog")));
+}
@@ -0,0 +1,32 @@
+use linked_data_sparql::{Sparql, ToConstructQuery};
+use spargebra::Query;
+use spargebra::term::Variable;
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[allow(dead_code)]
+#[derive(Sparql)]
+#[sparql(transparent)]
+struct Email(String);
+
+fn query<T: ToConstructQuery + ?Sized>() -> Query {
+  T::to_query_with_binding(Variable::new_unchecked("value"))
+    .guard_leaves()
+    .into()
+}
+
+#[test]
+fn test_smart_pointers() {
+  let expected = query::<String>();
+
+  assert_eq!(expected, query::<Box<String>>());
+  assert_eq!(expected, query::<Rc<String>>());
+  assert_eq!(expected, query::<Arc<String>>());
+  assert_eq!(expected, query::<Cow<'static, str>>());
+}
+
+#[test]
+fn test_transparent() {
+  assert_eq!(query::<String>(), query::<Email>());
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod all_tests;
 #[cfg(test)]
-mod rdf_type_conversions;
-#[cfg(test)]
 mod test_graph_store;
+#[cfg(test)]
+mod test_http_server;
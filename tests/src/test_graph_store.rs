@@ -1,5 +1,6 @@
-use crate::rdf_type_conversions::IntoRdfTypes;
 use linked_data_next::{LinkedData, to_quads_with};
+use linked_data_sparql::reexport::sparesults::QuerySolution;
+use linked_data_sparql::{Error, Executor, IntoRdfTypes};
 use oxigraph::sparql::QueryResults;
 use oxigraph::store::Store;
 use oxttl::NQuadsParser;
@@ -19,6 +20,10 @@ impl TestGraphStore {
     Self { store }
   }
 
+  pub fn store(&self) -> &Store {
+    &self.store
+  }
+
   pub fn insert(&mut self, data: &impl LinkedData<WithGenerator<Blank>>) {
     let mut interpretation = WithGenerator::new((), Blank::new());
 
@@ -40,18 +45,49 @@ impl TestGraphStore {
   }
 
   pub fn query(&self, query: spargebra::Query) -> IndexedBTreeDataset {
-    let mut expected_dataset = IndexedBTreeDataset::new();
+    self.construct(query).unwrap()
+  }
+}
 
-    if let QueryResults::Graph(triples) = self.store.query(query).unwrap() {
-      triples.filter_map(Result::ok).for_each(|triple| {
-        let quad = triple.into_rdf_types();
+impl Executor for TestGraphStore {
+  fn construct(&self, query: spargebra::Query) -> Result<IndexedBTreeDataset, Error> {
+    let mut dataset = IndexedBTreeDataset::new();
+
+    if let QueryResults::Graph(triples) = self.store.query(query).map_err(Error::transport)? {
+      for triple in triples {
+        let quad = triple.map_err(Error::transport)?.into_rdf_types()?;
         println!("{:?}", quad);
-        expected_dataset.insert(quad);
-      });
+        dataset.insert(quad);
+      }
     } else {
-      panic!();
+      return Err(Error::UnexpectedResults);
     }
 
-    expected_dataset
+    Ok(dataset)
+  }
+
+  fn select(&self, query: spargebra::Query) -> Result<Vec<QuerySolution>, Error> {
+    if let QueryResults::Solutions(solutions) = self.store.query(query).map_err(Error::transport)? {
+      solutions
+        .collect::<Result<_, _>>()
+        .map_err(Error::transport)
+    } else {
+      Err(Error::UnexpectedResults)
+    }
+  }
+
+  fn ask(&self, query: spargebra::Query) -> Result<bool, Error> {
+    if let QueryResults::Boolean(value) = self.store.query(query).map_err(Error::transport)? {
+      Ok(value)
+    } else {
+      Err(Error::UnexpectedResults)
+    }
+  }
+
+  fn update(&self, update: spargebra::Update) -> Result<(), Error> {
+    self
+      .store
+      .update(update.to_string().as_str())
+      .map_err(Error::transport)
   }
 }
@@ -0,0 +1,91 @@
+use oxigraph::io::RdfFormat;
+use oxigraph::sparql::QueryResults;
+use oxigraph::sparql::results::QueryResultsFormat;
+use oxigraph::store::Store;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+/// Minimal SPARQL 1.1 Protocol endpoint in front of an oxigraph store,
+/// serving `/query` and `/update` until the test process exits, and
+/// answering graph queries in `format`.
+pub fn serve(store: Store, format: RdfFormat) -> String {
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let address = listener.local_addr().unwrap();
+
+  std::thread::spawn(move || {
+    for stream in listener.incoming().filter_map(Result::ok) {
+      handle(&store, format, stream);
+    }
+  });
+
+  format!("http://{address}")
+}
+
+fn handle(store: &Store, format: RdfFormat, mut stream: TcpStream) {
+  let mut reader = BufReader::new(&mut stream);
+
+  let mut request_line = String::new();
+  reader.read_line(&mut request_line).unwrap();
+  let path = request_line
+    .split_whitespace()
+    .nth(1)
+    .unwrap_or_default()
+    .to_owned();
+
+  let mut content_length = 0;
+  loop {
+    let mut header = String::new();
+    reader.read_line(&mut header).unwrap();
+    let header = header.trim_end();
+    if header.is_empty() {
+      break;
+    }
+    if let Some((_, value)) = header
+      .split_once(':')
+      .filter(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+    {
+      content_length = value.trim().parse().unwrap();
+    }
+  }
+
+  let mut body = vec![0; content_length];
+  reader.read_exact(&mut body).unwrap();
+  let body = String::from_utf8(body).unwrap();
+
+  let (status, content_type, payload) = match path.as_str() {
+    "/query" => match store.query(body.as_str()) {
+      Ok(results @ QueryResults::Graph(_)) => (
+        "200 OK",
+        format.media_type(),
+        results.write_graph(Vec::new(), format).unwrap(),
+      ),
+      Ok(results) => (
+        "200 OK",
+        "application/sparql-results+json",
+        results.write(Vec::new(), QueryResultsFormat::Json).unwrap(),
+      ),
+      Err(error) => (
+        "400 Bad Request",
+        "text/plain",
+        error.to_string().into_bytes(),
+      ),
+    },
+    "/update" => match store.update(body.as_str()) {
+      Ok(()) => ("204 No Content", "text/plain", Vec::new()),
+      Err(error) => (
+        "400 Bad Request",
+        "text/plain",
+        error.to_string().into_bytes(),
+      ),
+    },
+    _ => ("404 Not Found", "text/plain", Vec::new()),
+  };
+
+  write!(
+    stream,
+    "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+    payload.len()
+  )
+  .unwrap();
+  stream.write_all(&payload).unwrap();
+}
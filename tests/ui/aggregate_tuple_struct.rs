@@ -0,0 +1,7 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Counts(#[ld("ex:followers")] #[sparql(count = "ex:follower")] u64);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:followers")]
+  #[sparql(count = "ex:follower", path = "ex:a/ex:b")]
+  followers: u64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[sparql(alias = "ex:label")]
+  name: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:hasFriend")]
+  #[sparql(bind = "EXISTS { ex:ada ex:knows ex:bob }")]
+  has_friend: bool,
+}
+
+fn main() {}
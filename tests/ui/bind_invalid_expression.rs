@@ -0,0 +1,14 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:birth")]
+  birth: i64,
+
+  #[ld("ex:year")]
+  #[sparql(bind = "?birth +")]
+  year: i64,
+}
+
+fn main() {}
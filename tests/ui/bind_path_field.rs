@@ -0,0 +1,15 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:birth")]
+  #[sparql(path = "ex:a/ex:b")]
+  birth: i64,
+
+  #[ld("ex:year")]
+  #[sparql(bind = "?birth + 1")]
+  year: i64,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Point(#[ld("ex:x")] i64, #[ld("ex:y")] #[sparql(bind = "1 + 1")] i64);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:year")]
+  #[sparql(bind = "YEAR(?birth)")]
+  year: i64,
+}
+
+fn main() {}
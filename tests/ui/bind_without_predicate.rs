@@ -0,0 +1,13 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:given")]
+  given: String,
+
+  #[sparql(bind = "UCASE(?given)")]
+  shout: String,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:address")]
+  #[ld(flatten)]
+  address: Address,
+}
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Address {
+  #[ld("ex:city")]
+  city: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:id")]
+  #[ld(id)]
+  id: iref::IriBuf,
+}
+
+fn main() {}
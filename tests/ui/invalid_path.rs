@@ -0,0 +1,11 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:city")]
+  #[sparql(path = "ex:address//ex:city")]
+  city: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "not an iri"))]
+struct Person {
+  #[ld("ex:name")]
+  name: String,
+}
+
+fn main() {}
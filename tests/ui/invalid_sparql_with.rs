@@ -0,0 +1,11 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:name")]
+  #[sparql(sparql_with = "not a path")]
+  name: String,
+}
+
+fn main() {}
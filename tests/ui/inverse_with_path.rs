@@ -0,0 +1,11 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:post")]
+  #[sparql(inverse = "ex:author", path = "ex:a/ex:b")]
+  posts: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+#[ld(type = ["ex:Person", "ex:Agent"])]
+#[ld(type_match = "subclass")]
+struct Person {
+  #[ld("ex:name")]
+  name: String,
+}
+
+#[derive(Sparql)]
+#[ld(transparent)]
+struct Name(String);
+
+fn main() {}
@@ -0,0 +1,17 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[ld("ex:name", lang = "en")]
+  name: String,
+
+  #[ld("ex:knows")]
+  #[ld(path = "ex:knows/ex:name")]
+  friend: String,
+
+  #[ld("ex:post", count = "ex:post")]
+  posts: i64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+struct Person {
+  #[sparql(path = "ex:address/ex:city")]
+  city: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[sparql(transparent)]
+enum Name {
+  Name(String),
+}
+
+fn main() {}
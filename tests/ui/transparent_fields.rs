@@ -0,0 +1,7 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[sparql(transparent)]
+struct Point(i64, i64);
+
+fn main() {}
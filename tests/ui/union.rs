@@ -0,0 +1,9 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+union Number {
+  integer: i64,
+  float: f64,
+}
+
+fn main() {}
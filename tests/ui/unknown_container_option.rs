@@ -0,0 +1,11 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+#[sparql(flatten)]
+struct Person {
+  #[ld("ex:name")]
+  name: String,
+}
+
+fn main() {}
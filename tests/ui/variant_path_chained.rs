@@ -0,0 +1,11 @@
+use linked_data_sparql::Sparql;
+
+#[derive(Sparql)]
+#[ld(prefix("ex" = "http://ex/"))]
+enum Contact {
+  #[ld("ex:email")]
+  #[sparql(path = "ex:email|ex:mail")]
+  Email(#[ld("ex:value")] String),
+}
+
+fn main() {}